@@ -1,14 +1,27 @@
 pub mod renderer;
 
 use std::error::Error;
+use std::fs::read_to_string;
 use std::io::{stdin, stdout, BufRead, Write};
 
 use crate::renderer::start_render_loop;
 use mplz_core::board::GameSession;
 use mplz_core::command::{AnalysisCommandArg, GameCommand};
+use mplz_core::replay::Replay;
+use mplz_core::serialization::StrategyInfo;
+use mplz_core::strategy::StrategyRegistry;
+use mplz_core::tournament::TournamentParams;
+
+///
+/// The number of snapshots kept for undoing.
+///
+const HISTORY_CAPACITY: usize = 100;
 
 fn main() -> Result<(), Box<dyn Error>> {
+    StrategyRegistry::install(StrategyRegistry::with_external())?;
+
     let mut game: Option<GameSession> = None;
+    let mut replay: Option<Replay> = None;
     loop {
         print!("$ ");
         stdout().flush().unwrap();
@@ -32,30 +45,120 @@ fn main() -> Result<(), Box<dyn Error>> {
                     GameCommand::Step(step, game).execute()?;
                 }
             }
+            (["undo" | "u"], Some(game)) => {
+                GameCommand::Undo(game).execute()?;
+            }
+            (["redo"], Some(game)) => {
+                GameCommand::Redo(game).execute()?;
+            }
+            (["goto", turn], Some(game)) => {
+                if let Ok(turn) = turn.parse::<usize>() {
+                    GameCommand::Goto(turn, game).execute()?;
+                }
+            }
             (["vmode" | "v"], Some(game)) => {
                 start_render_loop(game)?;
             }
             (["save" | "w", file_name], Some(game)) => {
                 GameCommand::Save(file_name, game).execute()?;
             }
+            (["ledger" | "l", file_name], Some(game)) => {
+                GameCommand::SaveLedger(file_name, game).execute()?;
+            }
+            (["audit", mode], Some(game)) => {
+                GameCommand::Audit(*mode == "on", game).execute()?;
+            }
+            (["audit"], Some(game)) => {
+                for violation in game.get_audit_violations() {
+                    println!("{}", violation);
+                }
+            }
+            (["record"], Some(game)) => {
+                GameCommand::Record(game).execute()?;
+            }
+            (["replay", "save", file_name], Some(game)) => {
+                GameCommand::SaveReplay(file_name, game).execute()?;
+            }
+            (["replay", "load", file_name], game) => {
+                GameCommand::LoadReplay(file_name, &mut replay, game).execute()?;
+            }
+            (["replay", "goto", turn], game) => {
+                if let (Ok(turn), Some(replay)) = (turn.parse::<usize>(), &replay) {
+                    GameCommand::GotoReplay(turn, replay, game).execute()?;
+                }
+            }
+            (["replay", "verify", file_name], _) => {
+                GameCommand::VerifyReplay(file_name).execute()?;
+            }
+            (["advise", player_id, iterations @ ..], Some(game)) => {
+                let iterations = iterations.first().map_or(Ok(200), |num| num.parse::<u32>());
+                if let (Ok(player_id), Ok(iterations)) = (player_id.parse::<usize>(), iterations) {
+                    GameCommand::Advise(player_id, iterations, game).execute()?;
+                }
+            }
+            (["tournament" | "t", games, max_turns, strategies @ ..], _) => {
+                if let (Ok(games), Ok(max_turns)) =
+                    (games.parse::<u32>(), max_turns.parse::<usize>())
+                {
+                    // Strategies exported by tuning can be given as JSON files.
+                    let strategies = strategies
+                        .iter()
+                        .map(|name| {
+                            if name.ends_with(".json") {
+                                read_to_string(name).map(|json| StrategyInfo::from_json(&json))
+                            } else {
+                                Ok(StrategyInfo::new(name))
+                            }
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let params = TournamentParams {
+                        strategies,
+                        games,
+                        max_turns,
+                        seed: None,
+                    };
+
+                    GameCommand::Tournament(params).execute()?;
+                }
+            }
+            (["tune", generations, checkpoint_file, output_file], _) => {
+                if let Ok(generations) = generations.parse::<u32>() {
+                    GameCommand::Tune {
+                        generations,
+                        checkpoint_file,
+                        output_file,
+                    }
+                    .execute()?;
+                }
+            }
             (["load" | "r", file_name], game) => {
                 GameCommand::Load(file_name, game).execute()?;
             }
-            (["analyze" | "a", file_name, iteration, simulation_turn], Some(game)) => {
+            (
+                ["analyze" | "a", file_name, iteration, simulation_turn, statistics_file @ ..],
+                Some(game),
+            ) => {
                 let file_name = file_name.to_string();
                 let iteration: i32 = iteration.parse().unwrap();
                 let simulation_turn: usize = simulation_turn.parse().unwrap();
+                let statistics_file = statistics_file.first().map(|name| name.to_string());
 
                 let arg = AnalysisCommandArg {
                     file_name,
                     iteration,
                     simulation_turn,
+                    statistics_file,
                 };
 
-                GameCommand::Analyze(arg, game).execute()?;
+                GameCommand::Analyze(arg, game, Vec::new()).execute()?;
             }
             _ => println!("Unknown command."),
         }
+
+        // Newly created games start keeping their history here.
+        if let Some(game) = &mut game {
+            game.set_history_capacity(HISTORY_CAPACITY);
+        }
     }
     Ok(())
 }
@@ -59,10 +59,18 @@ pub fn start_render_loop(game: &mut GameSession) -> Result<(), Box<dyn Error>> {
                 }
             }
             if key.code == KeyCode::Left {
-                terminal_selection = 0;
+                if key.modifiers.contains(KeyModifiers::SHIFT) {
+                    game.undo();
+                } else {
+                    terminal_selection = 0;
+                }
             }
             if key.code == KeyCode::Right {
-                terminal_selection = 1;
+                if key.modifiers.contains(KeyModifiers::SHIFT) {
+                    game.redo();
+                } else {
+                    terminal_selection = 1;
+                }
             }
         }
     }
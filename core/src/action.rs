@@ -0,0 +1,353 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+use crate::board::{game_log, GameSession};
+use crate::ledger::{Party, TransferReason};
+use crate::observer::{StrategyAction, TradeOffer};
+use crate::places::BoardPlace;
+
+///
+/// A decision which a player makes.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Decision {
+    /// Raises money to pay off the debt.
+    Raise(u32),
+    /// Invests at the end of a turn.
+    Invest,
+    /// Decides whether to buy the place for the price.
+    Buy(usize, u32),
+    /// Decides whether to pay the fee to leave the jail before rolling dices.
+    LeaveJail,
+}
+
+///
+/// An action which the game refuses to apply, with the reason.
+///
+#[derive(Debug, Clone)]
+pub struct IllegalAction {
+    pub player_id: usize,
+    pub action: StrategyAction,
+    pub reason: String,
+}
+
+impl Display for IllegalAction {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            fmt,
+            "[PLAYER{}] Illegal action {:?}: {}",
+            self.player_id, self.action, self.reason
+        )
+    }
+}
+
+impl Error for IllegalAction {}
+
+impl GameSession {
+    ///
+    /// Applies the actions in order.
+    ///
+    /// The illegal actions are skipped, logged and reported to the observers.
+    ///
+    pub fn apply_actions(&mut self, player_id: usize, actions: Vec<StrategyAction>) {
+        for action in actions {
+            if let Err(error) = self.apply_action(player_id, action) {
+                game_log!(self, Summary, "{}", error);
+                self.notify(|observer| observer.on_illegal_action(&error));
+            }
+        }
+    }
+
+    ///
+    /// Applies the actions other than `expected`, and returns whether `expected` is contained.
+    ///
+    /// `expected` is recorded as a decision, but it is up to the caller to apply it.
+    ///
+    pub(crate) fn apply_actions_except(
+        &mut self,
+        player_id: usize,
+        actions: Vec<StrategyAction>,
+        expected: &StrategyAction,
+    ) -> bool {
+        let mut contained = false;
+        for action in actions {
+            if action == *expected && !contained {
+                contained = true;
+            } else {
+                self.apply_actions(player_id, vec![action]);
+            }
+        }
+
+        if contained {
+            self.record_decision(player_id, expected);
+        }
+        contained
+    }
+
+    ///
+    /// Validates the action and applies it.
+    ///
+    /// `StrategyAction::Buy` and `StrategyAction::PayJailFee` are always refused here,
+    /// since they are taken only in response to `PlayerStrategy::buy` and `PlayerStrategy::leave_jail`.
+    ///
+    pub fn apply_action(
+        &mut self,
+        player_id: usize,
+        action: StrategyAction,
+    ) -> Result<(), IllegalAction> {
+        if let Err(reason) = self.validate_action(player_id, &action) {
+            return Err(IllegalAction {
+                player_id,
+                action,
+                reason,
+            });
+        }
+
+        let player = Party::Player(player_id);
+        match &action {
+            &StrategyAction::BuildHouse(id) => {
+                self.statistics.houses_built[player_id] += 1;
+
+                let cost = self.board.places[id].get_price_of_house().unwrap();
+                self.record_transfer(player, Party::Bank, cost, TransferReason::BuildHouse(id));
+
+                self.players[player_id].money -= cost;
+                let houses = self.board.places[id].get_num_houses().unwrap();
+                self.board.set_num_houses(id, houses + 1);
+            }
+            &StrategyAction::SellHouse(id) => {
+                self.statistics.houses_sold[player_id] += 1;
+
+                let price = self.board.places[id].get_price_of_house().unwrap() / 2;
+                self.record_transfer(Party::Bank, player, price, TransferReason::SellHouse(id));
+
+                self.players[player_id].money += price;
+                let houses = self.board.places[id].get_num_houses().unwrap();
+                self.board.set_num_houses(id, houses - 1);
+            }
+            &StrategyAction::Mortgage(id) => {
+                self.statistics.mortgages[player_id] += 1;
+
+                let price = self.board.places[id].get_price() / 2;
+                self.record_transfer(Party::Bank, player, price, TransferReason::Mortgage(id));
+
+                self.players[player_id].money += self.board.set_mortgaged(id, true);
+            }
+            &StrategyAction::Unmortgage(id) => {
+                let cost = self.board.places[id].get_return_cost();
+                self.record_transfer(player, Party::Bank, cost, TransferReason::Unmortgage(id));
+
+                self.players[player_id].money -= cost;
+                self.board.set_mortgaged(id, false);
+            }
+            StrategyAction::Trade(offer) => {
+                self.apply_trade(player_id, offer);
+            }
+            StrategyAction::Buy(_) | StrategyAction::PayJailFee => {
+                unreachable!();
+            }
+        }
+
+        self.record_decision(player_id, &action);
+
+        Ok(())
+    }
+
+    ///
+    /// Records the decision into the replay, and notifies the observers of it.
+    ///
+    pub(crate) fn record_decision(&mut self, player_id: usize, action: &StrategyAction) {
+        if let Some(turn) = self
+            .recording
+            .as_mut()
+            .and_then(|recording| recording.turns.last_mut())
+        {
+            turn.decisions.push((player_id, action.clone()));
+        }
+
+        self.notify(|observer| observer.on_strategy_action(player_id, action));
+    }
+
+    ///
+    /// Checks whether the player can take the action now.
+    ///
+    fn validate_action(&self, player_id: usize, action: &StrategyAction) -> Result<(), String> {
+        let player = &self.players[player_id];
+        if player.is_bankrupted() {
+            return Err("The player is bankrupted.".to_string());
+        }
+
+        let place_name = |id: usize| {
+            self.board
+                .places
+                .get(id)
+                .map(|place| place.get_place_name())
+                .ok_or_else(|| format!("There is no place {}.", id))
+        };
+
+        match action {
+            &StrategyAction::BuildHouse(id) => {
+                let name = place_name(id)?;
+                if !self.board.get_buildable_places(player_id).contains(&id) {
+                    return Err(format!("A house cannot be built on {}.", name));
+                }
+                if self.board.places[id].get_price_of_house().unwrap() > player.money {
+                    return Err(format!("The player cannot afford a house on {}.", name));
+                }
+            }
+            &StrategyAction::SellHouse(id) => {
+                let name = place_name(id)?;
+                if !self.board.get_sellable_places(player_id).contains(&id) {
+                    return Err(format!("A house cannot be sold from {}.", name));
+                }
+            }
+            &StrategyAction::Mortgage(id) => {
+                let name = place_name(id)?;
+                if !self.board.get_mortgageable_places(player_id).contains(&id) {
+                    return Err(format!("{} cannot be mortgaged.", name));
+                }
+            }
+            &StrategyAction::Unmortgage(id) => {
+                let name = place_name(id)?;
+                if !self
+                    .board
+                    .get_unmortgageable_places(player_id)
+                    .contains(&id)
+                {
+                    return Err(format!("{} cannot be unmortgaged.", name));
+                }
+                if self.board.places[id].get_return_cost() > player.money {
+                    return Err(format!("The player cannot afford to unmortgage {}.", name));
+                }
+            }
+            &StrategyAction::Buy(id) => {
+                let name = place_name(id)?;
+                return Err(format!("{} is not offered to the player.", name));
+            }
+            StrategyAction::PayJailFee => {
+                return Err("The player is not asked to leave the jail.".to_string());
+            }
+            StrategyAction::Trade(offer) => {
+                self.validate_trade(player_id, offer)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Checks whether the trade can be made, asking the partner whether to accept it.
+    ///
+    fn validate_trade(&self, player_id: usize, offer: &TradeOffer) -> Result<(), String> {
+        let partner_id = offer.partner;
+        if partner_id == player_id || partner_id >= self.players.len() {
+            return Err(format!("PLAYER{} cannot be a partner.", partner_id));
+        }
+        if self.players[partner_id].is_bankrupted() {
+            return Err(format!("PLAYER{} is bankrupted.", partner_id));
+        }
+        if offer.give.is_empty()
+            && offer.take.is_empty()
+            && offer.give_money == 0
+            && offer.take_money == 0
+        {
+            return Err("The offer is empty.".to_string());
+        }
+
+        let mut places = HashSet::new();
+        let owned_places = offer
+            .give
+            .iter()
+            .map(|&id| (id, player_id))
+            .chain(offer.take.iter().map(|&id| (id, partner_id)));
+        for (id, owner) in owned_places {
+            let Some(place) = self.board.places.get(id) else {
+                return Err(format!("There is no place {}.", id));
+            };
+            if !places.insert(id) {
+                return Err(format!("{} appears twice.", place.get_place_name()));
+            }
+            if place.get_owner() != Some(owner) {
+                return Err(format!(
+                    "{} is not owned by PLAYER{}.",
+                    place.get_place_name(),
+                    owner
+                ));
+            }
+            if place.is_estate() && self.board.get_houses_num_by_color(place.get_color()) != Some(0)
+            {
+                return Err(format!(
+                    "The houses of the color of {} have to be sold first.",
+                    place.get_place_name()
+                ));
+            }
+        }
+
+        if self.players[player_id].money < offer.give_money {
+            return Err("The player cannot afford the money offered.".to_string());
+        }
+        if self.players[partner_id].money < offer.take_money {
+            return Err(format!(
+                "PLAYER{} cannot afford the money requested.",
+                partner_id
+            ));
+        }
+
+        let partner = &self.players[partner_id];
+        let view = self.get_view(partner_id);
+        if !partner
+            .get_strategy()
+            .accepts_trade(&view, player_id, offer)
+        {
+            return Err(format!("PLAYER{} declines the trade.", partner_id));
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Exchanges the places and the money between the players.
+    ///
+    fn apply_trade(&mut self, player_id: usize, offer: &TradeOffer) {
+        let partner_id = offer.partner;
+        let transfers = [
+            (player_id, partner_id, offer.give_money, &offer.give),
+            (partner_id, player_id, offer.take_money, &offer.take),
+        ];
+        for (from, to, money, places) in transfers {
+            if money > 0 {
+                self.record_transfer(
+                    Party::Player(from),
+                    Party::Player(to),
+                    money,
+                    TransferReason::Trade,
+                );
+
+                self.players[from].money -= money;
+                self.players[to].money += money;
+            }
+
+            for &id in places {
+                self.record_transfer(
+                    Party::Player(from),
+                    Party::Player(to),
+                    0,
+                    TransferReason::TradePlace(id),
+                );
+
+                self.board.set_owner(id, Some(to));
+            }
+        }
+
+        game_log!(
+            self,
+            Summary,
+            "[PLAYER{}] Trades with PLAYER{}.",
+            player_id,
+            partner_id
+        );
+    }
+}
@@ -0,0 +1,754 @@
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::action::Decision;
+use crate::appraiser::Appraiser;
+use crate::board::{Board, GameSession, JAIL_FEE};
+use crate::events::EventKind;
+use crate::observer::{StrategyAction, TradeOffer};
+use crate::places::{BoardColor, BoardPlace};
+use crate::player::PlayerState;
+use crate::serialization::StrategyInfo;
+use crate::statistics::get_wilson_interval;
+use crate::strategy::{
+    build_within, decide, get_monopolized_colors, ExpensiveHousesProtectionStrategy, GameView,
+    MonteCarloStrategy, Plan, PlayerStrategy, RandomStrategy, RiskAwareStrategy, StrategyRegistry,
+};
+
+///
+/// Holds the parameters of `Advisor`.
+///
+/// `iterations` is the number of simulations in total, each of which plays `depth` turns after the first decision.
+/// `rollout` is the strategy of the other players, and of the player below the search tree.
+/// If `seed` is `None`, a random seed is used.
+///
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdvisorParams {
+    pub iterations: u32,
+    pub depth: usize,
+    pub exploration: f64,
+    pub rollout: StrategyInfo,
+    pub seed: Option<u64>,
+}
+
+impl Default for AdvisorParams {
+    fn default() -> Self {
+        AdvisorParams {
+            iterations: 200,
+            depth: 50,
+            exploration: std::f64::consts::SQRT_2,
+            rollout: StrategyInfo::new(ExpensiveHousesProtectionStrategy::NAME),
+            seed: None,
+        }
+    }
+}
+
+///
+/// A candidate of actions evaluated by `Advisor`.
+///
+/// `confidence_interval` is the 95% Wilson score interval of the win rate.
+///
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankedAction {
+    pub actions: Vec<StrategyAction>,
+    pub win_rate: f64,
+    pub visits: u32,
+    pub confidence_interval: (f64, f64),
+}
+
+///
+/// Evaluates candidate actions by Monte Carlo tree search.
+///
+/// The tree grows over the decisions of the player: each node holds the candidates of the follow-up decisions,
+/// which are selected by UCB1 and expanded one per simulation. Since the dices and the other players are not
+/// modeled in the tree, the nodes are keyed by the decisions and the actions taken, not by the states of the game.
+/// Below the tree, the game is played out with the rollout strategy, counting whether the player wins,
+/// that is, they survive with the largest payable money.
+///
+#[derive(Clone)]
+pub struct Advisor {
+    params: AdvisorParams,
+    rng: StdRng,
+}
+
+impl Advisor {
+    ///
+    /// Generates an advisor.
+    ///
+    pub fn new(params: AdvisorParams) -> Self {
+        let rng = StdRng::seed_from_u64(params.seed.unwrap_or_else(rand::random));
+        Advisor { params, rng }
+    }
+
+    ///
+    /// Reseeds the random number generator of the rollouts.
+    ///
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    ///
+    /// Ranks the candidate actions for the decision in descending order of the win rate.
+    ///
+    /// Fails if the decision cannot be made by the player, e.g. when the place is not for sale.
+    ///
+    pub fn advise(
+        &mut self,
+        view: &GameView,
+        decision: &Decision,
+    ) -> Result<Vec<RankedAction>, String> {
+        validate_decision(view, decision)?;
+        let candidates = get_candidates(view, decision, true);
+
+        let mut game = view.to_session();
+        for player_id in 0..game.players.len() {
+            game.get_player_mut(player_id)
+                .set_strategy(self.create_rollout_strategy());
+        }
+
+        let tree = Arc::new(Mutex::new(SearchTree::new(self.params.exploration)));
+        if candidates.len() > 1 {
+            for _ in 0..self.params.iterations {
+                let actions = tree.lock().unwrap().select(decision, candidates.clone());
+                let score = self.simulate(&game, view.player_id, decision, actions, &tree);
+                tree.lock().unwrap().backpropagate(score);
+            }
+        }
+
+        let tree = tree.lock().unwrap();
+        let mut ranked = candidates
+            .into_iter()
+            .map(|actions| {
+                let (wins, visits) = tree.get_root_result(&actions);
+                let win_rate = if visits > 0 {
+                    wins / visits as f64
+                } else {
+                    0.0
+                };
+                RankedAction {
+                    actions,
+                    win_rate,
+                    visits,
+                    confidence_interval: get_wilson_interval(win_rate, visits),
+                }
+            })
+            .collect::<Vec<_>>();
+        ranked.sort_by(|a, b| b.win_rate.total_cmp(&a.win_rate));
+        Ok(ranked)
+    }
+
+    ///
+    /// Plays out the game after the player takes the actions, and returns the score of the player.
+    ///
+    /// The follow-up decisions of the player are made by the tree until it is expanded.
+    ///
+    fn simulate(
+        &mut self,
+        game: &GameSession,
+        player_id: usize,
+        decision: &Decision,
+        actions: Vec<StrategyAction>,
+        tree: &Arc<Mutex<SearchTree>>,
+    ) -> f64 {
+        let mut game = game.clone();
+        game.set_seed(self.rng.gen());
+
+        let strategy = Box::new(TreeStrategy {
+            tree: tree.clone(),
+            fallback: self.create_rollout_strategy(),
+        });
+        match *decision {
+            Decision::Raise(debt) => {
+                // Lets the engine collect the debt, forcing the player to take the actions to raise money.
+                // Only rent is paid to the other players, so the owner of the place is the creditor if any.
+                game.turn = player_id;
+                force(&mut game, player_id, decision, actions, strategy.clone());
+                let position = game.get_player(player_id).position;
+                match game.board.places[position].get_owner() {
+                    Some(owner) if owner != player_id => {
+                        game.invoke_event(EventKind::PayToOther("the debt", owner, debt))
+                    }
+                    _ => game.invoke_event(EventKind::PayToBank("the debt", debt)),
+                }
+                game.get_player_mut(player_id).set_strategy(strategy);
+                game.turn = (game.turn + 1) % game.players.len();
+            }
+            Decision::Invest => {
+                game.apply_actions(player_id, actions);
+                game.get_player_mut(player_id).set_strategy(strategy);
+                game.turn = (game.turn + 1) % game.players.len();
+            }
+            Decision::Buy(place_id, price) => {
+                // Lets the engine sell the place, forcing the player to take the actions.
+                game.turn = player_id;
+                force(&mut game, player_id, decision, actions, strategy.clone());
+                game.invoke_event(EventKind::GivePlace(place_id, price));
+                game.get_player_mut(player_id).set_strategy(strategy);
+                game.turn = (game.turn + 1) % game.players.len();
+            }
+            Decision::LeaveJail => {
+                // Lets the engine handle the jail, forcing the player to take the actions.
+                force(&mut game, player_id, decision, actions, strategy);
+                game.spend_one_turn();
+            }
+        }
+
+        for _ in 0..self.params.depth {
+            if game.count_active_players() <= 1 {
+                break;
+            }
+            game.spend_one_turn();
+        }
+
+        get_score(&game, player_id)
+    }
+
+    ///
+    /// Creates the strategy used for the rollouts.
+    ///
+    fn create_rollout_strategy(&self) -> Box<dyn PlayerStrategy + Send> {
+        // The search nested in every rollout would never finish in time.
+        if self.params.rollout.name == MonteCarloStrategy::NAME {
+            return ExpensiveHousesProtectionStrategy::new_boxed();
+        }
+        StrategyRegistry::get_builtin()
+            .create(&self.params.rollout)
+            .unwrap_or_else(|_| ExpensiveHousesProtectionStrategy::new_boxed())
+    }
+}
+
+impl GameSession {
+    ///
+    /// Ranks the candidate actions of the player for the decision by Monte Carlo tree search.
+    ///
+    /// Fails if the decision cannot be made by the player, e.g. when the place is not for sale.
+    ///
+    pub fn get_advice(
+        &self,
+        player_id: usize,
+        decision: &Decision,
+        params: &AdvisorParams,
+    ) -> Result<Vec<RankedAction>, String> {
+        Advisor::new(params.clone()).advise(&self.get_view(player_id), decision)
+    }
+}
+
+///
+/// Makes the player take the actions on the first chance of the decision, and follow the fallback otherwise.
+///
+fn force(
+    game: &mut GameSession,
+    player_id: usize,
+    decision: &Decision,
+    actions: Vec<StrategyAction>,
+    fallback: Box<dyn PlayerStrategy + Send>,
+) {
+    game.get_player_mut(player_id)
+        .set_strategy(Box::new(ForcedDecision {
+            decision: decision.clone(),
+            actions: RefCell::new(Some(actions)),
+            fallback,
+        }));
+}
+
+///
+/// Takes the forced actions on the first chance of the decision, and follows the fallback otherwise.
+///
+/// `Decision::Invest` is never forced, since the actions are applied directly.
+///
+#[derive(Clone)]
+struct ForcedDecision {
+    decision: Decision,
+    actions: RefCell<Option<Vec<StrategyAction>>>,
+    fallback: Box<dyn PlayerStrategy + Send>,
+}
+
+impl PlayerStrategy for ForcedDecision {
+    fn raise(&self, view: &GameView, debt: u32) -> Vec<StrategyAction> {
+        let forced = match self.decision {
+            Decision::Raise(_) => self.actions.borrow_mut().take(),
+            _ => None,
+        };
+        forced.unwrap_or_else(|| self.fallback.raise(view, debt))
+    }
+
+    fn invest(&self, view: &GameView) -> Vec<StrategyAction> {
+        self.fallback.invest(view)
+    }
+
+    fn buy(&self, view: &GameView, place_id: usize, price: u32) -> Vec<StrategyAction> {
+        let forced = match self.decision {
+            Decision::Buy(..) => self.actions.borrow_mut().take(),
+            _ => None,
+        };
+        forced.unwrap_or_else(|| self.fallback.buy(view, place_id, price))
+    }
+
+    fn leave_jail(&self, view: &GameView) -> Vec<StrategyAction> {
+        let forced = match self.decision {
+            Decision::LeaveJail => self.actions.borrow_mut().take(),
+            _ => None,
+        };
+        forced.unwrap_or_else(|| self.fallback.leave_jail(view))
+    }
+
+    fn accepts_trade(&self, view: &GameView, proposer: usize, offer: &TradeOffer) -> bool {
+        self.fallback.accepts_trade(view, proposer, offer)
+    }
+
+    fn get_info(&self) -> StrategyInfo {
+        self.fallback.get_info()
+    }
+
+    fn take_reports(&self) -> Vec<String> {
+        self.fallback.take_reports()
+    }
+
+    fn set_seed(&self, seed: u64) {
+        self.fallback.set_seed(seed);
+    }
+
+    fn clone_boxed(&self) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(self.clone())
+    }
+}
+
+///
+/// A node of the search tree, holding the candidates of every decision which follows.
+///
+#[derive(Default)]
+struct SearchNode {
+    branches: Vec<SearchBranch>,
+}
+
+///
+/// The candidates of a decision which have been tried.
+///
+struct SearchBranch {
+    decision: Decision,
+    children: Vec<SearchChild>,
+}
+
+///
+/// A candidate of actions with the scores of the simulations through it.
+///
+struct SearchChild {
+    actions: Vec<StrategyAction>,
+    visits: u32,
+    wins: f64,
+    node: SearchNode,
+}
+
+///
+/// The search tree of `Advisor` with the path which the current simulation takes.
+///
+/// `expanded` tells whether the simulation has added a node, after which it plays out with the rollout strategy.
+///
+struct SearchTree {
+    root: SearchNode,
+    path: Vec<(usize, usize)>,
+    expanded: bool,
+    exploration: f64,
+}
+
+impl SearchTree {
+    fn new(exploration: f64) -> Self {
+        SearchTree {
+            root: SearchNode::default(),
+            path: Vec::new(),
+            expanded: false,
+            exploration,
+        }
+    }
+
+    ///
+    /// Selects one of the candidates for the decision at the end of the path, and extends the path with it.
+    ///
+    /// A candidate never tried is expanded first. Otherwise, the one with the largest UCB1 is selected.
+    ///
+    fn select(
+        &mut self,
+        decision: &Decision,
+        candidates: Vec<Vec<StrategyAction>>,
+    ) -> Vec<StrategyAction> {
+        let exploration = self.exploration;
+        let mut node = &mut self.root;
+        for &(branch_id, child_id) in &self.path {
+            node = &mut node.branches[branch_id].children[child_id].node;
+        }
+
+        let branch_id = match node
+            .branches
+            .iter()
+            .position(|branch| &branch.decision == decision)
+        {
+            Some(branch_id) => branch_id,
+            None => {
+                node.branches.push(SearchBranch {
+                    decision: decision.clone(),
+                    children: Vec::new(),
+                });
+                node.branches.len() - 1
+            }
+        };
+        let children = &mut node.branches[branch_id].children;
+
+        let tried = candidates
+            .iter()
+            .map(|actions| children.iter().position(|child| &child.actions == actions))
+            .collect::<Vec<_>>();
+        if let Some(id) = tried.iter().position(|child_id| child_id.is_none()) {
+            let actions = candidates[id].clone();
+            children.push(SearchChild {
+                actions: actions.clone(),
+                visits: 0,
+                wins: 0.0,
+                node: SearchNode::default(),
+            });
+            self.path.push((branch_id, children.len() - 1));
+            self.expanded = true;
+            return actions;
+        }
+
+        // Only the candidates available now compete, since the tree does not tell the states apart.
+        let tried = tried.into_iter().flatten().collect::<Vec<_>>();
+        let total = tried
+            .iter()
+            .map(|&child_id| children[child_id].visits)
+            .sum::<u32>()
+            .max(1);
+        let ucb = |child: &SearchChild| {
+            child.wins / child.visits as f64
+                + exploration * ((total as f64).ln() / child.visits as f64).sqrt()
+        };
+        let child_id = tried
+            .into_iter()
+            .max_by(|&a, &b| ucb(&children[a]).total_cmp(&ucb(&children[b])))
+            .unwrap();
+        self.path.push((branch_id, child_id));
+        children[child_id].actions.clone()
+    }
+
+    ///
+    /// Adds the score of the simulation to the nodes on the path, and clears the path for the next one.
+    ///
+    fn backpropagate(&mut self, score: f64) {
+        let mut node = &mut self.root;
+        for (branch_id, child_id) in self.path.drain(..) {
+            let child = &mut node.branches[branch_id].children[child_id];
+            child.visits += 1;
+            child.wins += score;
+            node = &mut child.node;
+        }
+        self.expanded = false;
+    }
+
+    ///
+    /// Gets the total score and the visits of the candidate for the first decision.
+    ///
+    fn get_root_result(&self, actions: &[StrategyAction]) -> (f64, u32) {
+        self.root
+            .branches
+            .first()
+            .and_then(|branch| {
+                branch
+                    .children
+                    .iter()
+                    .find(|child| child.actions == actions)
+            })
+            .map_or((0.0, 0), |child| (child.wins, child.visits))
+    }
+}
+
+///
+/// Makes the decisions of the player in a simulation by the search tree, and by the fallback below it.
+///
+#[derive(Clone)]
+struct TreeStrategy {
+    tree: Arc<Mutex<SearchTree>>,
+    fallback: Box<dyn PlayerStrategy + Send>,
+}
+
+impl TreeStrategy {
+    fn decide(&self, view: &GameView, decision: Decision) -> Vec<StrategyAction> {
+        let mut tree = self.tree.lock().unwrap();
+        if !tree.expanded && !view.get_me().is_bankrupted() {
+            // The follow-up decisions are many, so the slow strategies are not asked for them.
+            let candidates = get_candidates(view, &decision, false);
+            if candidates.len() > 1 {
+                return tree.select(&decision, candidates);
+            }
+        }
+        drop(tree);
+        decide(self.fallback.as_ref(), view, &decision)
+    }
+}
+
+impl PlayerStrategy for TreeStrategy {
+    fn raise(&self, view: &GameView, debt: u32) -> Vec<StrategyAction> {
+        self.decide(view, Decision::Raise(debt))
+    }
+
+    fn invest(&self, view: &GameView) -> Vec<StrategyAction> {
+        self.decide(view, Decision::Invest)
+    }
+
+    fn buy(&self, view: &GameView, place_id: usize, price: u32) -> Vec<StrategyAction> {
+        self.decide(view, Decision::Buy(place_id, price))
+    }
+
+    fn leave_jail(&self, view: &GameView) -> Vec<StrategyAction> {
+        self.decide(view, Decision::LeaveJail)
+    }
+
+    fn accepts_trade(&self, view: &GameView, proposer: usize, offer: &TradeOffer) -> bool {
+        self.fallback.accepts_trade(view, proposer, offer)
+    }
+
+    fn get_info(&self) -> StrategyInfo {
+        self.fallback.get_info()
+    }
+
+    fn take_reports(&self) -> Vec<String> {
+        self.fallback.take_reports()
+    }
+
+    fn set_seed(&self, seed: u64) {
+        self.fallback.set_seed(seed);
+    }
+
+    fn clone_boxed(&self) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(self.clone())
+    }
+}
+
+///
+/// Checks that the player can be asked for the decision now.
+///
+fn validate_decision(view: &GameView, decision: &Decision) -> Result<(), String> {
+    if view.get_me().is_bankrupted() {
+        return Err(format!("PLAYER{} is bankrupted.", view.player_id));
+    }
+
+    match *decision {
+        Decision::Buy(place_id, _) => {
+            if !view
+                .get_legal_actions(decision)
+                .contains(&StrategyAction::Buy(place_id))
+            {
+                return Err(format!("The place {} is not for sale.", place_id));
+            }
+        }
+        Decision::LeaveJail => {
+            if !matches!(view.get_me().state, PlayerState::InJail(_)) {
+                return Err(format!("PLAYER{} is not in the jail.", view.player_id));
+            }
+        }
+        Decision::Raise(_) | Decision::Invest => {}
+    }
+    Ok(())
+}
+
+///
+/// Lists up the candidates of actions for the decision.
+///
+/// Besides the simple choices, the candidates include what the built-in strategies would do if it is legal.
+/// `RiskAwareStrategy`, which computes the distributions of the rents, is asked only if `thorough` is set.
+///
+fn get_candidates(
+    view: &GameView,
+    decision: &Decision,
+    thorough: bool,
+) -> Vec<Vec<StrategyAction>> {
+    let mut candidates = vec![Vec::new()];
+    let mut push = |actions: Vec<StrategyAction>| {
+        if !candidates.contains(&actions) {
+            candidates.push(actions);
+        }
+    };
+
+    match *decision {
+        Decision::Buy(place_id, _) => {
+            if !view.get_legal_actions(decision).is_empty() {
+                push(vec![StrategyAction::Buy(place_id)]);
+            }
+        }
+        Decision::LeaveJail => {
+            if view
+                .get_legal_actions(decision)
+                .contains(&StrategyAction::PayJailFee)
+                && JAIL_FEE <= view.get_money()
+            {
+                push(vec![StrategyAction::PayJailFee]);
+            }
+        }
+        Decision::Invest => {
+            // Builds the houses on each color up to each level.
+            for color in get_monopolized_colors(view.board, view.player_id) {
+                for level in 1..=5 {
+                    if let Some(actions) = get_building_to(view, &color, level) {
+                        push(actions);
+                    }
+                }
+            }
+        }
+        Decision::Raise(debt) => {
+            // Raises the money mortgaging the places first, selling the houses first,
+            // or liquidating each color first.
+            let player_id = view.player_id;
+            let raisings = [
+                get_raising(view, debt, |board| {
+                    get_mortgage(board, player_id, None)
+                        .or_else(|| get_sale(board, player_id, None))
+                }),
+                get_raising(view, debt, |board| {
+                    get_sale(board, player_id, None)
+                        .or_else(|| get_mortgage(board, player_id, None))
+                }),
+            ];
+            for actions in raisings.into_iter().flatten() {
+                push(actions);
+            }
+
+            for color in get_monopolized_colors(view.board, player_id) {
+                if view
+                    .board
+                    .gets_by_color(color.clone())
+                    .all(|place| place.get_num_houses() == Some(0))
+                {
+                    continue;
+                }
+                let actions = get_raising(view, debt, |board| {
+                    get_sale(board, player_id, Some(&color))
+                        .or_else(|| get_mortgage(board, player_id, Some(&color)))
+                        .or_else(|| get_mortgage(board, player_id, None))
+                        .or_else(|| get_sale(board, player_id, None))
+                });
+                if let Some(actions) = actions {
+                    push(actions);
+                }
+            }
+        }
+    }
+
+    let registry = StrategyRegistry::get_builtin();
+    for name in registry.get_names() {
+        if name == MonteCarloStrategy::NAME
+            || name == RandomStrategy::NAME
+            || (name == RiskAwareStrategy::NAME && !thorough)
+        {
+            continue;
+        }
+        let Ok(strategy) = registry.create(&StrategyInfo::new(name)) else {
+            continue;
+        };
+
+        let actions = decide(strategy.as_ref(), view, decision);
+        if !candidates.contains(&actions) && view.validate_actions(decision, &actions).is_ok() {
+            candidates.push(actions);
+        }
+    }
+
+    candidates
+}
+
+///
+/// Gets the actions to raise money for the debt, taking the action chosen on the board each time.
+///
+/// Returns `None` if the player cannot raise enough money that way.
+///
+fn get_raising(
+    view: &GameView,
+    debt: u32,
+    mut next: impl FnMut(&Board) -> Option<StrategyAction>,
+) -> Option<Vec<StrategyAction>> {
+    let mut game = view.to_session();
+    let mut actions = Vec::new();
+    while game.get_player(view.player_id).money < debt {
+        let action = next(&game.board)?;
+        game.apply_action(view.player_id, action.clone()).ok()?;
+        actions.push(action);
+    }
+    Some(actions)
+}
+
+///
+/// Gets the action to mortgage the cheapest place of the player, on the color if given.
+///
+fn get_mortgage(
+    board: &Board,
+    player_id: usize,
+    color: Option<&BoardColor>,
+) -> Option<StrategyAction> {
+    board
+        .get_mortgageable_places(player_id)
+        .into_iter()
+        .filter(|&id| color.is_none_or(|color| &board.places[id].get_color() == color))
+        .min_by_key(|&id| board.places[id].get_price())
+        .map(StrategyAction::Mortgage)
+}
+
+///
+/// Gets the action to sell the most expensive house of the player, on the color if given.
+///
+fn get_sale(board: &Board, player_id: usize, color: Option<&BoardColor>) -> Option<StrategyAction> {
+    board
+        .get_sellable_places(player_id)
+        .into_iter()
+        .filter(|&id| color.is_none_or(|color| &board.places[id].get_color() == color))
+        .max_by_key(|&id| board.places[id].get_price_of_house())
+        .map(StrategyAction::SellHouse)
+}
+
+///
+/// Gets the actions to build houses on the color up to the level, if the player can afford them.
+///
+fn get_building_to(view: &GameView, color: &BoardColor, level: u8) -> Option<Vec<StrategyAction>> {
+    let places = view.board.gets_by_color(color.clone()).collect::<Vec<_>>();
+    if places.iter().any(|place| place.is_mortgaged())
+        || places
+            .iter()
+            .all(|place| place.get_num_houses().unwrap() >= level)
+    {
+        return None;
+    }
+
+    let mut plan = Plan::new(view);
+    let mut usable = plan.get_money();
+    if build_within(&mut plan, color, level, &mut usable) {
+        Some(plan.into_actions())
+    } else {
+        None
+    }
+}
+
+///
+/// Gets the score of the player: 1 if they win, shared among the players tied, and 0 otherwise.
+///
+pub(crate) fn get_score(game: &GameSession, player_id: usize) -> f64 {
+    if game.get_player(player_id).is_bankrupted() {
+        return 0.0;
+    }
+
+    let wealth = game
+        .players
+        .iter()
+        .filter(|player| !player.is_bankrupted())
+        .map(|player| Appraiser::get_payable_money(player, &game.board))
+        .collect::<Vec<_>>();
+    let own_wealth = Appraiser::get_payable_money(game.get_player(player_id), &game.board);
+    let max_wealth = *wealth.iter().max().unwrap();
+    if own_wealth == max_wealth {
+        1.0 / wealth
+            .iter()
+            .filter(|&&wealth| wealth == max_wealth)
+            .count() as f64
+    } else {
+        0.0
+    }
+}
@@ -1,6 +1,6 @@
 use crate::board::Board;
 use crate::events::EventKind;
-use crate::places::BoardColor;
+use crate::places::{BoardColor, BoardPlace};
 use crate::player::Player;
 
 ///
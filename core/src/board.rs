@@ -1,8 +1,15 @@
+use std::hash::{Hash, Hasher};
+
 use crate::appraiser::Appraiser;
 use crate::dice_rolling::{DiceResult, DiceRolling};
 use crate::events::EventKind;
-use crate::places::{get_place_list, BoardColor, BoardPlace};
+use crate::history::History;
+use crate::ledger::{Auditor, Ledger, Party, Transfer, TransferReason};
+use crate::observer::{GameObserver, StrategyAction};
+use crate::places::{get_place_list, BoardColor, BoardPlace, Place};
 use crate::player::{Player, PlayerState};
+use crate::replay::{Replay, ReplayTurn};
+use crate::statistics::GameStatistics;
 use crate::strategy::ExpensiveHousesProtectionStrategy;
 
 ///
@@ -10,7 +17,12 @@ use crate::strategy::ExpensiveHousesProtectionStrategy;
 ///
 /// In details, it determines the place where the player is sent when they complete their term.
 ///
-const JAIL_POSITION: usize = 10;
+pub const JAIL_POSITION: usize = 10;
+
+///
+/// A fee to leave the jail.
+///
+pub const JAIL_FEE: u32 = 50;
 
 ///
 /// A fragment of the summary of the game.
@@ -48,6 +60,19 @@ impl GameSummaryFragment {
     }
 }
 
+///
+/// Determines how much a game session logs.
+///
+/// `Summary` logs only the major events, such as purchases, trades and bankruptcies.
+/// With `Off`, no text is formatted at all, which suits bulk simulations.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Off,
+    Summary,
+    Full,
+}
+
 ///
 /// Represents a game.
 ///
@@ -57,18 +82,60 @@ pub struct GameSession {
     pub players: Vec<Player>,
     pub board: Board,
     pub turn: usize,
+    pub turn_count: usize,
     pub logs: Vec<String>,
+    pub ledger: Ledger,
+    pub statistics: GameStatistics,
+    pub(crate) seed: u64,
+    pub(crate) dice: DiceRolling,
+    pub(crate) recording: Option<Replay>,
+    pub(crate) history: History,
+    log_level: LogLevel,
+    auditor: Option<Auditor>,
+    observers: Vec<Box<dyn GameObserver + Send>>,
 }
 
 ///
-/// Log a formatted text inside `MonopolyGame`.
+/// Log a formatted text inside `MonopolyGame` if the log level is `$level` or more verbose.
+///
+/// The text is not formatted unless it is logged.
 ///
 macro_rules! game_log {
-    ($self:tt, $fmt:tt,$($x:expr),*) => {
-        $self.logs.push(format!($fmt, $($x,)*));
+    ($self:tt, $level:ident, $fmt:tt $(, $x:expr)*) => {
+        if $self.is_logging($crate::board::LogLevel::$level) {
+            $self.logs.push(format!($fmt $(, $x)*));
+        }
     };
 }
 
+pub(crate) use game_log;
+
+impl Clone for GameSession {
+    ///
+    /// Clones the session with the state of the strategies and the dices.
+    ///
+    /// The observers are not cloned, since they watch the original game.
+    ///
+    fn clone(&self) -> Self {
+        GameSession {
+            players: self.players.clone(),
+            board: self.board.clone(),
+            turn: self.turn,
+            turn_count: self.turn_count,
+            logs: self.logs.clone(),
+            ledger: self.ledger.clone(),
+            statistics: self.statistics.clone(),
+            seed: self.seed,
+            dice: self.dice.clone(),
+            recording: self.recording.clone(),
+            history: self.history.clone(),
+            log_level: self.log_level,
+            auditor: self.auditor.clone(),
+            observers: Vec::new(),
+        }
+    }
+}
+
 impl GameSession {
     ///
     /// Generates a game.
@@ -78,12 +145,112 @@ impl GameSession {
             .map(|id| Player::new(id as usize, ExpensiveHousesProtectionStrategy::new_boxed()))
             .collect::<Vec<_>>();
 
+        let board = Board::new();
+        let statistics = GameStatistics::new(players.len(), board.places.len());
+        let seed = rand::random();
+
         GameSession {
             players,
-            board: Board::new(),
+            board,
             turn: 0,
+            turn_count: 0,
             logs: Vec::new(),
+            ledger: Ledger::default(),
+            statistics,
+            seed,
+            dice: DiceRolling::new(seed),
+            recording: None,
+            history: History::default(),
+            log_level: LogLevel::Full,
+            auditor: None,
+            observers: Vec::new(),
+        }
+    }
+
+    ///
+    /// Gets the seed of the dices.
+    ///
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    ///
+    /// Resets the dices with the seed.
+    ///
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.dice = DiceRolling::new(seed);
+    }
+
+    ///
+    /// Gets how much the session logs.
+    ///
+    pub fn get_log_level(&self) -> LogLevel {
+        self.log_level
+    }
+
+    ///
+    /// Sets how much the session logs. It is `LogLevel::Full` by default.
+    ///
+    pub fn set_log_level(&mut self, log_level: LogLevel) {
+        self.log_level = log_level;
+    }
+
+    ///
+    /// Gets whether the session logs the texts of the level.
+    ///
+    pub fn is_logging(&self, level: LogLevel) -> bool {
+        level != LogLevel::Off && level <= self.log_level
+    }
+
+    ///
+    /// Makes the dices show the designated faces in order before using the seed.
+    ///
+    pub fn script_dices<I: IntoIterator<Item = (u32, u32)>>(&mut self, faces: I) {
+        self.dice.script(faces);
+    }
+
+    ///
+    /// Rolls dices, recording the faces if needed.
+    ///
+    fn roll_dices(&mut self) -> DiceResult {
+        let faces = self.dice.roll();
+        if let Some(turn) = self
+            .recording
+            .as_mut()
+            .and_then(|recording| recording.turns.last_mut())
+        {
+            turn.rolls.push(faces);
         }
+        DiceResult::from_faces(faces)
+    }
+
+    ///
+    /// Registers an observer which gets notified of what happens in the game.
+    ///
+    pub fn add_observer(&mut self, observer: Box<dyn GameObserver + Send>) {
+        self.observers.push(observer);
+    }
+
+    ///
+    /// Removes all of the observers registered.
+    ///
+    pub fn clear_observers(&mut self) {
+        self.observers.clear();
+    }
+
+    ///
+    /// Gets whether one of the observers requests to stop the game.
+    ///
+    pub fn should_stop(&self) -> bool {
+        self.observers.iter().any(|observer| observer.should_stop())
+    }
+
+    ///
+    /// Notifies the observers.
+    ///
+    pub(crate) fn notify<F: FnMut(&mut Box<dyn GameObserver + Send>)>(&mut self, f: F) {
+        self.observers.iter_mut().for_each(f);
     }
 
     ///
@@ -131,54 +298,156 @@ impl GameSession {
         self.get_player_mut(self.turn)
     }
 
+    ///
+    /// Records a transfer in the ledger.
+    ///
+    /// **Call this before the board changes, since the auditor values the assets on the current board.**
+    ///
+    pub fn record_transfer(
+        &mut self,
+        payer: Party,
+        payee: Party,
+        amount: u32,
+        reason: TransferReason,
+    ) {
+        let transfer = Transfer {
+            turn: self.turn_count,
+            payer,
+            payee,
+            amount,
+            reason,
+        };
+
+        if let (TransferReason::Rent(place), Party::Player(payer), Party::Player(payee)) =
+            (&transfer.reason, payer, payee)
+        {
+            self.statistics.rent_by_place[*place] += amount;
+            self.statistics.rent_paid[payer] += amount;
+            self.statistics.rent_received[payee] += amount;
+        }
+
+        if let Some(auditor) = &mut self.auditor {
+            let asset_change = self.board.get_asset_change(&transfer.reason);
+            auditor.expect(&transfer, asset_change);
+        }
+        self.notify(|observer| observer.on_transfer(&transfer));
+
+        self.ledger.record(transfer);
+    }
+
+    ///
+    /// Logs what the strategy of the player has reported.
+    ///
+    fn log_reports(&mut self, player_id: usize) {
+        for msg in self.players[player_id].get_strategy().take_reports() {
+            game_log!(self, Summary, "[PLAYER{}] {}", player_id, msg);
+        }
+    }
+
+    ///
+    /// Makes the player pay money, asking their strategy to raise money if needed.
+    ///
+    fn pay(&mut self, player_id: usize, dollars: u32) -> Result<(), u32> {
+        if self.players[player_id].money < dollars {
+            let start = self.get_recorded_decision_num();
+            let view = self.get_view(player_id);
+            let actions = self.players[player_id].get_strategy().raise(&view, dollars);
+            self.apply_actions(player_id, actions);
+            self.record_call(player_id, start);
+            self.log_reports(player_id);
+        }
+
+        let result = self.players[player_id].pay(dollars);
+        match result {
+            Ok(_) => game_log!(
+                self,
+                Full,
+                "[PLAYER{}] Money: ${} -> ${}",
+                player_id,
+                self.players[player_id].money + dollars,
+                self.players[player_id].money
+            ),
+            Err(_) => game_log!(self, Summary, "[PLAYER{}] Bankrupted", player_id),
+        }
+
+        result
+    }
+
+    ///
+    /// Makes the current player pay money to the bank.
+    ///
+    /// If the player cannot pay, they get bankrupted and their properties will be returned to the bank.
+    ///
+    fn pay_to_bank(&mut self, dollars: u32, reason: TransferReason) {
+        let turn = self.turn;
+
+        match self.pay(turn, dollars) {
+            Ok(_) => {
+                self.record_transfer(Party::Player(turn), Party::Bank, dollars, reason);
+            }
+            Err(money) => {
+                self.record_transfer(
+                    Party::Player(turn),
+                    Party::Bank,
+                    money,
+                    TransferReason::Bankruptcy,
+                );
+                self.notify(|observer| observer.on_bankrupted(turn));
+
+                game_log!(
+                    self,
+                    Summary,
+                    "[PLAYER{}] All of the properties are returned to the bank.",
+                    turn
+                );
+
+                // Reset the properties.
+                let player_places = self.board.get_owned_places(turn).to_vec();
+                for id in player_places {
+                    self.record_transfer(
+                        Party::Player(turn),
+                        Party::Bank,
+                        0,
+                        TransferReason::Foreclosure(id),
+                    );
+
+                    self.board.set_owner(id, None);
+                    self.board.set_mortgaged(id, false);
+                    if self.board.places[id].is_estate() {
+                        self.board.set_num_houses(id, 0);
+                    }
+                }
+            }
+        }
+    }
+
     ///
     /// Invokes the event.
     ///
     pub fn invoke_event(&mut self, event: EventKind) {
         let turn = self.turn;
+        self.notify(|observer| observer.on_event(turn, &event));
+
         match event {
             EventKind::None(msg) => {
-                game_log!(self, "[PLAYER{}] {}", turn, msg);
+                game_log!(self, Full, "[PLAYER{}] {}", turn, msg);
             }
             EventKind::PayToBank(msg, dollars) => {
                 game_log!(
                     self,
+                    Full,
                     "[PLAYER{}] Pays ${} to the bank for {}.",
                     turn,
                     dollars,
                     msg
                 );
 
-                let current_player = &mut self.players[turn];
-                let (result, mut logs) = current_player.pay(&mut self.board, dollars);
-                self.logs.append(&mut logs);
-
-                // If the player cannot pay, their property will be returned to the bank.
-                if result.is_err() {
-                    game_log!(
-                        self,
-                        "[PLAYER{}] All of the properties are returned to the bank.",
-                        turn
-                    );
-
-                    // Reset the properties.
-                    let player_places = self
-                        .board
-                        .places
-                        .iter_mut()
-                        .filter(|place| place.get_owner() == Some(turn));
-                    for place in player_places {
-                        place.set_owner(None);
-                        place.set_mortgaged(false);
-                        if place.is_estate() {
-                            place.set_num_houses(0);
-                        }
-                    }
-                }
+                self.pay_to_bank(dollars, TransferReason::Fee(msg.to_string()));
             }
             EventKind::PayToOther(msg, receiver, dollars) => {
                 game_log!(
                     self,
+                    Full,
                     "[PLAYER{}] Pays ${} to PLAYER{} for {}.",
                     turn,
                     dollars,
@@ -186,16 +455,28 @@ impl GameSession {
                     msg
                 );
 
-                let current_player = &mut self.players[turn];
-                let (result, mut logs) = current_player.pay(&mut self.board, dollars);
-                self.logs.append(&mut logs);
-
-                match result {
+                let position = self.get_current_player().position;
+                match self.pay(turn, dollars) {
                     Ok(_) => {
+                        self.record_transfer(
+                            Party::Player(turn),
+                            Party::Player(receiver),
+                            dollars,
+                            TransferReason::Rent(position),
+                        );
+
                         let receiver = self.get_player_mut(receiver);
                         receiver.money += dollars;
                     }
                     Err(money) => {
+                        self.record_transfer(
+                            Party::Player(turn),
+                            Party::Player(receiver),
+                            money,
+                            TransferReason::Bankruptcy,
+                        );
+                        self.notify(|observer| observer.on_bankrupted(turn));
+
                         // When the player cannot pay, their properties will be moved to the creditor.
                         let receiver = self.get_player_mut(receiver);
                         receiver.money += money;
@@ -203,30 +484,52 @@ impl GameSession {
 
                         game_log!(
                             self,
+                            Summary,
                             "[PLAYER{}] Inherits properties of PLAYER{}.",
                             receiver_id,
                             turn
                         );
 
-                        let player_places = self
-                            .board
-                            .places
-                            .iter_mut()
-                            .filter(|place| place.get_owner() == Some(turn));
-                        for place in player_places {
-                            place.set_owner(Some(receiver_id));
+                        let player_places = self.board.get_owned_places(turn).to_vec();
+                        for id in player_places {
+                            self.record_transfer(
+                                Party::Player(turn),
+                                Party::Player(receiver_id),
+                                0,
+                                TransferReason::Inheritance(id),
+                            );
+
+                            self.board.set_owner(id, Some(receiver_id));
                         }
                     }
                 }
             }
+            EventKind::PayToOtherByDice(msg, receiver, multiplier) => {
+                let dollars = self.roll_dices().unwrap() * multiplier;
+                self.invoke_event(EventKind::PayToOther(msg, receiver, dollars));
+            }
             EventKind::Reward(msg, dollars) => {
-                game_log!(self, "[PLAYER{}] Gains ${} for {}.", turn, dollars, msg);
+                game_log!(
+                    self,
+                    Full,
+                    "[PLAYER{}] Gains ${} for {}.",
+                    turn,
+                    dollars,
+                    msg
+                );
+
+                self.record_transfer(
+                    Party::Bank,
+                    Party::Player(turn),
+                    dollars,
+                    TransferReason::Reward(msg.to_string()),
+                );
 
                 let current_player = self.get_current_player_mut();
                 current_player.money += dollars;
             }
             EventKind::Move(msg, mut place) => {
-                game_log!(self, "[PLAYER{}] Needs to move for {}.", turn, msg);
+                game_log!(self, Full, "[PLAYER{}] Needs to move for {}.", turn, msg);
 
                 let current_player = self.get_current_player_mut();
                 let position = current_player.position;
@@ -238,23 +541,53 @@ impl GameSession {
             EventKind::GivePlace(place, dollars) => {
                 let place_name = self.board.places[place].get_place_name();
 
+                let start = self.get_recorded_decision_num();
+                let view = self.get_view(turn);
+                let actions = self.players[turn].get_strategy().buy(&view, place, dollars);
+
+                // Buys the place after the other actions, which may raise money for it.
+                let buys = self.apply_actions_except(turn, actions, &StrategyAction::Buy(place));
+                self.record_call(turn, start);
+                self.log_reports(turn);
+                if !buys {
+                    game_log!(
+                        self,
+                        Full,
+                        "[PLAYER{}] Declines to buy {}.",
+                        turn,
+                        place_name
+                    );
+                    return;
+                }
+
                 game_log!(
                     self,
+                    Summary,
                     "[PLAYER{}] Buys {} for ${}.",
                     turn,
                     place_name,
                     dollars
                 );
+                game_log!(
+                    self,
+                    Full,
+                    "[PLAYER{}] Pays ${} to the bank for {}.",
+                    turn,
+                    dollars,
+                    place_name
+                );
 
-                self.invoke_event(EventKind::PayToBank(place_name, dollars));
+                self.pay_to_bank(dollars, TransferReason::Purchase(place));
 
                 let current_player = self.get_current_player();
                 if current_player.state != PlayerState::Bankrupted {
-                    self.board.places[place].set_owner(Some(turn));
+                    self.board.set_owner(place, Some(turn));
                 }
             }
             EventKind::GetJailed => {
-                game_log!(self, "[PLAYER{}] Gets jailed.", turn);
+                game_log!(self, Summary, "[PLAYER{}] Gets jailed.", turn);
+
+                self.statistics.jail_entries[turn] += 1;
 
                 let current_player = self.get_current_player_mut();
                 current_player.state = PlayerState::InJail(0);
@@ -276,6 +609,7 @@ impl GameSession {
         if new_position >= self.board.places.len() {
             new_position -= self.board.places.len();
 
+            self.statistics.go_passes[self.turn] += 1;
             self.invoke_event(EventKind::Reward("passing GO", 200))
         }
 
@@ -283,20 +617,75 @@ impl GameSession {
         current_player.position = new_position;
         let new_position_name = self.board.places[new_position].get_place_name();
 
-        self.invoke_event(EventKind::None(&format!(
-            "Moves from {} to {}.",
-            previous_position_name, new_position_name
-        )));
+        let turn = self.turn;
+        self.statistics.landings[new_position] += 1;
+        self.notify(|observer| observer.on_move(turn, previous_position, new_position));
+
+        // The observers see the move through `on_move`, so the message is needed only for the logs.
+        if self.is_logging(LogLevel::Full) {
+            self.invoke_event(EventKind::None(&format!(
+                "Moves from {} to {}.",
+                previous_position_name, new_position_name
+            )));
+        }
 
         self.invoke_event(self.board.places[new_position].get_action(self.turn, &self.board));
     }
 
+    ///
+    /// Enables or disables the auditing mode.
+    ///
+    /// In the auditing mode, the session checks that money is conserved after every turn.
+    ///
+    pub fn set_auditing(&mut self, enabled: bool) {
+        self.auditor = if enabled { Some(Auditor::new()) } else { None };
+    }
+
+    ///
+    /// Gets the violations found in the auditing mode.
+    ///
+    pub fn get_audit_violations(&self) -> &[String] {
+        self.auditor
+            .as_ref()
+            .map_or(&[], |auditor| auditor.violations.as_slice())
+    }
+
+    ///
+    /// Gets the sum of the payable money of the players, which is regarded as their wealth by the auditor.
+    ///
+    fn get_total_wealth(&self) -> i64 {
+        self.players
+            .iter()
+            .map(|player| Appraiser::get_payable_money(player, &self.board) as i64)
+            .sum()
+    }
+
     ///
     /// Emulates a turn.
     ///
     /// This method takes account of the effect of the same number.
     ///
     pub fn spend_one_turn(&mut self) {
+        self.save_to_history();
+
+        let turn = self.turn;
+        if let Some(recording) = &mut self.recording {
+            recording.turns.push(ReplayTurn {
+                player_id: turn,
+                rolls: Vec::new(),
+                decisions: Vec::new(),
+                calls: Vec::new(),
+            });
+        }
+
+        if self.auditor.is_some() {
+            let money = self.players.iter().map(|player| player.money).collect();
+            let wealth = self.get_total_wealth();
+            if let Some(auditor) = &mut self.auditor {
+                auditor.begin(money, wealth, &self.ledger);
+            }
+        }
+
         if self.count_active_players() > 1 {
             self.spend_one_turn_internal(0);
         }
@@ -304,8 +693,17 @@ impl GameSession {
         // Since the number of active players can be changed through moving,
         // check it again here.
         if self.count_active_players() > 1 {
-            for player in &mut self.players {
-                player.invest(&mut self.board);
+            for player_id in 0..self.players.len() {
+                if self.players[player_id].is_bankrupted() {
+                    continue;
+                }
+
+                let start = self.get_recorded_decision_num();
+                let view = self.get_view(player_id);
+                let actions = self.players[player_id].get_strategy().invest(&view);
+                self.apply_actions(player_id, actions);
+                self.record_call(player_id, start);
+                self.log_reports(player_id);
             }
 
             self.turn += 1;
@@ -313,6 +711,35 @@ impl GameSession {
                 self.turn -= self.players.len();
             }
         }
+
+        if self.auditor.is_some() {
+            let money = self
+                .players
+                .iter()
+                .map(|player| player.money)
+                .collect::<Vec<_>>();
+            let wealth = self.get_total_wealth();
+            if let Some(auditor) = &mut self.auditor {
+                let mut violations = auditor.end(self.turn_count, &money, wealth, &self.ledger);
+                if self.is_logging(LogLevel::Summary) {
+                    self.logs.append(&mut violations);
+                }
+            }
+        }
+
+        self.turn_count += 1;
+        for player in &self.players {
+            if player.state != PlayerState::Bankrupted {
+                self.statistics.turns_survived[player.player_id] += 1;
+            }
+        }
+
+        // Takes the observers out temporarily since they need to see the whole session.
+        let mut observers = std::mem::take(&mut self.observers);
+        for observer in &mut observers {
+            observer.on_turn_end(self);
+        }
+        self.observers = observers;
     }
 
     ///
@@ -328,12 +755,12 @@ impl GameSession {
         }
 
         let turn = self.turn;
-        let current_player = self.get_current_player_mut();
+        let current_player = self.get_current_player();
 
         assert_eq!(turn, current_player.player_id);
 
         match current_player.state {
-            PlayerState::None => match DiceRolling::roll() {
+            PlayerState::None => match self.roll_dices() {
                 DiceResult::Same(result) => {
                     self.move_player(result as usize);
 
@@ -350,24 +777,47 @@ impl GameSession {
             PlayerState::Bankrupted => {
                 self.invoke_event(EventKind::None("Already bankrupted."));
             }
-            PlayerState::InJail(jail_count) => match DiceRolling::roll() {
-                DiceResult::Same(result) => {
-                    current_player.state = PlayerState::None;
-                    self.invoke_event(EventKind::None("Leaves the jail."));
-                    self.move_player(result as usize);
+            PlayerState::InJail(jail_count) => {
+                let start = self.get_recorded_decision_num();
+                let view = self.get_view(turn);
+                let actions = self.players[turn].get_strategy().leave_jail(&view);
+                let pays = self.apply_actions_except(turn, actions, &StrategyAction::PayJailFee);
+                self.record_call(turn, start);
+                self.log_reports(turn);
+                if pays {
+                    self.get_current_player_mut().state = PlayerState::None;
+                    self.statistics.jail_exits[turn] += 1;
+                    self.invoke_event(EventKind::None("Pays to leave the jail."));
+                    self.invoke_event(EventKind::PayToBank("Jail fee", JAIL_FEE));
+
+                    if !self.get_current_player().is_bankrupted() {
+                        self.spend_one_turn_internal(recursion);
+                    }
+                    return;
                 }
-                DiceResult::Different(result) => {
-                    if jail_count == 2 {
-                        current_player.state = PlayerState::None;
-                        self.invoke_event(EventKind::None("Completes their term of jail."));
-                        self.invoke_event(EventKind::PayToBank("Jail fee", 50));
+
+                match self.roll_dices() {
+                    DiceResult::Same(result) => {
+                        self.get_current_player_mut().state = PlayerState::None;
+                        self.statistics.jail_exits[turn] += 1;
+                        self.invoke_event(EventKind::None("Leaves the jail."));
                         self.move_player(result as usize);
-                    } else {
-                        current_player.state = PlayerState::InJail(jail_count + 1);
-                        self.invoke_event(EventKind::None("Stays in the jail."));
+                    }
+                    DiceResult::Different(result) => {
+                        if jail_count == 2 {
+                            self.get_current_player_mut().state = PlayerState::None;
+                            self.statistics.jail_exits[turn] += 1;
+                            self.invoke_event(EventKind::None("Completes their term of jail."));
+                            self.invoke_event(EventKind::PayToBank("Jail fee", JAIL_FEE));
+                            self.move_player(result as usize);
+                        } else {
+                            self.get_current_player_mut().state =
+                                PlayerState::InJail(jail_count + 1);
+                            self.invoke_event(EventKind::None("Stays in the jail."));
+                        }
                     }
                 }
-            },
+            }
         }
     }
 
@@ -383,13 +833,55 @@ impl GameSession {
     }
 }
 
+///
+/// Indices of a board which are kept up to date on every change of ownership, mortgage and houses.
+///
+/// The counts are indexed by the color and then by the owner.
+///
+#[derive(Clone, Default)]
+struct BoardIndex {
+    by_color: Vec<Vec<usize>>,
+    by_owner: Vec<Vec<usize>>,
+    owned_counts: Vec<Vec<u32>>,
+    active_counts: Vec<Vec<u32>>,
+    monopolizers: Vec<Option<usize>>,
+    rents: Vec<u32>,
+    max_rents: Vec<u32>,
+    bank_rent: u32,
+}
+
+impl BoardIndex {
+    ///
+    /// Extends the indices so that they can hold the places of the owner.
+    ///
+    fn add_owner(&mut self, owner: usize) {
+        if owner >= self.by_owner.len() {
+            self.by_owner.resize(owner + 1, Vec::new());
+            self.max_rents.resize(owner + 1, 0);
+            for counts in self.owned_counts.iter_mut().chain(&mut self.active_counts) {
+                counts.resize(owner + 1, 0);
+            }
+        }
+    }
+}
+
+///
+/// The number of the colors including `BoardColor::None`.
+///
+const COLOR_NUM: usize = 11;
+
 ///
 /// Represents a board.
 ///
 /// This can be seen as a set of places with some useful functions.
+/// Ownership, mortgages and houses should be changed through `Board`, not through the places,
+/// so that the indices of the board are kept up to date.
+/// If the places are modified directly, call `Board::reindex` afterwards.
 ///
+#[derive(Clone)]
 pub struct Board {
-    pub places: Vec<Box<dyn BoardPlace + Send>>,
+    pub places: Vec<Place>,
+    index: BoardIndex,
 }
 
 impl Default for Board {
@@ -398,14 +890,196 @@ impl Default for Board {
     }
 }
 
+// The indices are derived from the places, so only the places are compared.
+impl PartialEq for Board {
+    fn eq(&self, other: &Self) -> bool {
+        self.places == other.places
+    }
+}
+
+impl Eq for Board {}
+
+impl Hash for Board {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.places.hash(state);
+    }
+}
+
 impl Board {
     ///
     /// Generates a board.
     ///
     pub fn new() -> Self {
-        Board {
+        let mut board = Board {
             places: get_place_list(),
+            index: BoardIndex::default(),
+        };
+        board.reindex();
+        board
+    }
+
+    ///
+    /// Rebuilds the indices of the board from scratch.
+    ///
+    /// This has to be called after modifying the places directly through `places` or `gets_by_color_mut`.
+    ///
+    pub fn reindex(&mut self) {
+        let mut index = BoardIndex {
+            by_color: vec![Vec::new(); COLOR_NUM],
+            owned_counts: vec![Vec::new(); COLOR_NUM],
+            active_counts: vec![Vec::new(); COLOR_NUM],
+            monopolizers: vec![None; COLOR_NUM],
+            rents: vec![0; self.places.len()],
+            ..BoardIndex::default()
+        };
+        for place in &self.places {
+            index.by_color[place.get_color() as usize].push(place.get_id());
+            if let Some(owner) = place.get_owner() {
+                index.add_owner(owner);
+                index.by_owner[owner].push(place.get_id());
+            }
         }
+        self.index = index;
+
+        for color in 0..COLOR_NUM {
+            self.update_counts(color);
+        }
+        // Rents depend on the counts, so they are calculated after all the counts are updated.
+        for id in 0..self.places.len() {
+            self.index.rents[id] = self.places[id].get_rent(self).unwrap_or(0);
+        }
+        self.index.bank_rent = self
+            .places
+            .iter()
+            .filter(|place| !place.is_property())
+            .map(|place| self.index.rents[place.get_id()])
+            .max()
+            .unwrap_or(0);
+        for owner in 0..self.index.by_owner.len() {
+            self.update_max_rent(owner);
+        }
+    }
+
+    ///
+    /// Sets the owner of the place.
+    ///
+    pub fn set_owner(&mut self, place_id: usize, owner: Option<usize>) {
+        let previous = self.places[place_id].get_owner();
+        self.places[place_id].set_owner(owner);
+
+        if let Some(previous) = previous {
+            self.index.by_owner[previous].retain(|&id| id != place_id);
+        }
+        if let Some(owner) = owner {
+            self.index.add_owner(owner);
+            let places = &mut self.index.by_owner[owner];
+            let pos = places.partition_point(|&id| id < place_id);
+            places.insert(pos, place_id);
+        }
+
+        self.update_place(place_id, previous);
+    }
+
+    ///
+    /// Sets whether the place is mortgaged, and returns the money the bank pays for mortgaging it.
+    ///
+    pub fn set_mortgaged(&mut self, place_id: usize, mortgaged: bool) -> u32 {
+        let price = self.places[place_id].set_mortgaged(mortgaged);
+        self.update_place(place_id, None);
+        price
+    }
+
+    ///
+    /// Sets the number of houses on the place.
+    ///
+    pub fn set_num_houses(&mut self, place_id: usize, num: u8) {
+        self.places[place_id].set_num_houses(num);
+        self.update_place(place_id, None);
+    }
+
+    ///
+    /// Updates the indices after the place is changed.
+    ///
+    /// `previous` is the owner who has just lost the place, if any.
+    ///
+    fn update_place(&mut self, place_id: usize, previous: Option<usize>) {
+        let color = self.places[place_id].get_color() as usize;
+        self.update_counts(color);
+
+        // Changing one place may change the rents of the other places of the same color.
+        let mut owners = previous.into_iter().collect::<Vec<_>>();
+        for i in 0..self.index.by_color[color].len() {
+            let id = self.index.by_color[color][i];
+            self.index.rents[id] = self.places[id].get_rent(self).unwrap_or(0);
+            owners.extend(self.places[id].get_owner());
+        }
+
+        owners.sort_unstable();
+        owners.dedup();
+        for owner in owners {
+            self.update_max_rent(owner);
+        }
+    }
+
+    ///
+    /// Recounts the places of the color for each owner.
+    ///
+    fn update_counts(&mut self, color: usize) {
+        let index = &mut self.index;
+        let owners = index.by_owner.len();
+        let (owned, active) = (
+            &mut index.owned_counts[color],
+            &mut index.active_counts[color],
+        );
+        owned.clear();
+        owned.resize(owners, 0);
+        active.clear();
+        active.resize(owners, 0);
+
+        for &id in &index.by_color[color] {
+            let place = &self.places[id];
+            if let Some(owner) = place.get_owner() {
+                owned[owner] += 1;
+                if !place.is_mortgaged() {
+                    active[owner] += 1;
+                }
+            }
+        }
+
+        let total = index.by_color[color].len() as u32;
+        index.monopolizers[color] = owned.iter().position(|&count| count == total);
+    }
+
+    ///
+    /// Recalculates the most expensive rent among the places of the owner.
+    ///
+    fn update_max_rent(&mut self, owner: usize) {
+        let index = &mut self.index;
+        index.max_rents[owner] = index.by_owner[owner]
+            .iter()
+            .map(|&id| index.rents[id])
+            .max()
+            .unwrap_or(0);
+    }
+
+    ///
+    /// Gets ids of the places the player owns in ascending order.
+    ///
+    pub fn get_owned_places(&self, player_id: usize) -> &[usize] {
+        self.index
+            .by_owner
+            .get(player_id)
+            .map_or(&[], |places| places.as_slice())
+    }
+
+    ///
+    /// Gets the number of the unmortgaged places of the color which the player owns.
+    ///
+    pub fn get_own_num(&self, color: BoardColor, player_id: usize) -> u32 {
+        self.index.active_counts[color as usize]
+            .get(player_id)
+            .copied()
+            .unwrap_or(0)
     }
 
     ///
@@ -413,10 +1087,8 @@ impl Board {
     ///
     pub fn get_houses_num_by_color(&self, color: BoardColor) -> Option<u8> {
         let mut num = 0;
-        for place in &self.places {
-            if place.get_color() == color {
-                num += place.get_num_houses()?
-            }
+        for place in self.gets_by_color(color) {
+            num += place.get_num_houses()?
         }
         Some(num)
     }
@@ -424,22 +1096,18 @@ impl Board {
     ///
     /// Gets an iterator of places with the designated color.
     ///
-    pub fn gets_by_color(
-        &self,
-        color: BoardColor,
-    ) -> impl Iterator<Item = &Box<dyn BoardPlace + Send>> {
-        self.places
+    pub fn gets_by_color(&self, color: BoardColor) -> impl Iterator<Item = &Place> {
+        self.index.by_color[color as usize]
             .iter()
-            .filter(move |place| place.get_color() == color)
+            .map(|&id| &self.places[id])
     }
 
     ///
     /// Gets a mutable iterator of places with the designated color.
     ///
-    pub fn gets_by_color_mut(
-        &mut self,
-        color: BoardColor,
-    ) -> impl Iterator<Item = &mut Box<dyn BoardPlace + Send>> {
+    /// Call `Board::reindex` after modifying the places.
+    ///
+    pub fn gets_by_color_mut(&mut self, color: BoardColor) -> impl Iterator<Item = &mut Place> {
         self.places
             .iter_mut()
             .filter(move |place| place.get_color() == color)
@@ -449,17 +1117,7 @@ impl Board {
     /// Gets a monopolizer of the color if exists.
     ///
     pub fn get_monopolizer(&self, color: BoardColor) -> Option<usize> {
-        let mut owners = self.places.iter().filter_map(|place| {
-            if place.get_color() == color {
-                Some(place.get_owner())
-            } else {
-                None
-            }
-        });
-        owners
-            .next()
-            .unwrap()
-            .filter(|&possible_owner| owners.all(|owner| owner == Some(possible_owner)))
+        self.index.monopolizers[color as usize]
     }
 
     ///
@@ -498,19 +1156,342 @@ impl Board {
     }
 
     ///
-    /// Gets the most expensive
+    /// Gets ids of the places on which the player can build a house under the rule of building flatly.
+    ///
+    /// Money is not taken into account.
+    ///
+    pub fn get_buildable_places(&self, player_id: usize) -> Vec<usize> {
+        let mut buildable = Vec::new();
+        for color in BoardColor::get_estate_colors() {
+            if self.get_monopolizer(color.clone()) != Some(player_id) {
+                continue;
+            }
+
+            let places = self.gets_by_color(color).collect::<Vec<_>>();
+            if places.iter().any(|place| place.is_mortgaged()) {
+                continue;
+            }
+
+            let min_houses = places
+                .iter()
+                .map(|place| place.get_num_houses().unwrap())
+                .min()
+                .unwrap();
+            buildable.extend(
+                places
+                    .iter()
+                    .filter(|place| place.get_num_houses().unwrap() == min_houses)
+                    .filter(|place| place.get_num_houses().unwrap() < 5)
+                    .map(|place| place.get_id()),
+            );
+        }
+        buildable
+    }
+
+    ///
+    /// Gets ids of the places from which the player can sell a house under the rule of building flatly.
+    ///
+    pub fn get_sellable_places(&self, player_id: usize) -> Vec<usize> {
+        let mut sellable = Vec::new();
+        for color in BoardColor::get_estate_colors() {
+            if self.get_monopolizer(color.clone()) != Some(player_id) {
+                continue;
+            }
+
+            let places = self.gets_by_color(color).collect::<Vec<_>>();
+            let max_houses = places
+                .iter()
+                .map(|place| place.get_num_houses().unwrap())
+                .max()
+                .unwrap();
+            if max_houses > 0 {
+                sellable.extend(
+                    places
+                        .iter()
+                        .filter(|place| place.get_num_houses().unwrap() == max_houses)
+                        .map(|place| place.get_id()),
+                );
+            }
+        }
+        sellable
+    }
+
+    ///
+    /// Gets ids of the places the player can mortgage.
+    ///
+    /// A place cannot be mortgaged while any place of the same color has houses.
+    ///
+    pub fn get_mortgageable_places(&self, player_id: usize) -> Vec<usize> {
+        self.get_owned_places(player_id)
+            .iter()
+            .map(|&id| &self.places[id])
+            .filter(|place| !place.is_mortgaged())
+            .filter(|place| {
+                !place.is_estate() || self.get_houses_num_by_color(place.get_color()) == Some(0)
+            })
+            .map(|place| place.get_id())
+            .collect()
+    }
+
+    ///
+    /// Gets ids of the places the player can unmortgage.
+    ///
+    /// Money is not taken into account.
+    ///
+    pub fn get_unmortgageable_places(&self, player_id: usize) -> Vec<usize> {
+        self.get_owned_places(player_id)
+            .iter()
+            .copied()
+            .filter(|&id| self.places[id].is_mortgaged())
+            .collect()
+    }
+
+    ///
+    /// Gets the most expensive rent which the player may have to pay.
     ///
     pub fn get_most_expensive(&self, excluding: usize) -> u32 {
-        let mut most_expensive = 0u32;
-        for place in &self.places {
-            if place.get_owner() != Some(excluding) {
-                let rent = place.get_rent(self).unwrap_or(0);
+        self.index
+            .max_rents
+            .iter()
+            .enumerate()
+            .filter(|&(owner, _)| owner != excluding)
+            .map(|(_, &rent)| rent)
+            .fold(self.index.bank_rent, u32::max)
+    }
 
-                if rent > most_expensive {
-                    most_expensive = rent;
+    ///
+    /// Calculates how much the transfer changes the asset value of the players.
+    ///
+    /// Assets are valued at the price the bank pays for them.
+    ///
+    pub fn get_asset_change(&self, reason: &TransferReason) -> i64 {
+        match *reason {
+            TransferReason::Purchase(id) | TransferReason::Unmortgage(id) => {
+                (self.places[id].get_price() / 2) as i64
+            }
+            TransferReason::Mortgage(id) => -((self.places[id].get_price() / 2) as i64),
+            TransferReason::BuildHouse(id) => {
+                (self.places[id].get_price_of_house().unwrap() / 2) as i64
+            }
+            TransferReason::SellHouse(id) => {
+                -((self.places[id].get_price_of_house().unwrap() / 2) as i64)
+            }
+            TransferReason::Foreclosure(id) => {
+                let place = &self.places[id];
+                if place.is_mortgaged() {
+                    0
+                } else {
+                    -((place.get_price() / 2
+                        + place.get_price_of_house().unwrap_or(0)
+                            * place.get_num_houses().unwrap_or(0) as u32
+                            / 2) as i64)
                 }
             }
+            _ => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use crate::board::Board;
+    use crate::places::{BoardColor, BoardPlace};
+    use crate::serialization::PlaceInfo;
+
+    const PLAYER_NUM: usize = 4;
+
+    ///
+    /// Checks the indexed queries against scanning all of the places.
+    ///
+    /// The rents are calculated on a board indexed from scratch.
+    ///
+    fn assert_indexed(board: &Board) {
+        let mut fresh = board.clone();
+        fresh.reindex();
+
+        let mut colors: Vec<BoardColor> = Vec::new();
+        for place in &board.places {
+            if !colors.contains(&place.get_color()) {
+                colors.push(place.get_color());
+            }
+        }
+
+        for player_id in 0..PLAYER_NUM {
+            let owned = board
+                .places
+                .iter()
+                .filter(|place| place.get_owner() == Some(player_id))
+                .map(|place| place.get_id())
+                .collect::<Vec<_>>();
+            assert_eq!(board.get_owned_places(player_id), owned.as_slice());
+
+            for color in &colors {
+                let own_num = board
+                    .places
+                    .iter()
+                    .filter(|place| {
+                        place.get_color() == *color
+                            && place.get_owner() == Some(player_id)
+                            && !place.is_mortgaged()
+                    })
+                    .count() as u32;
+                assert_eq!(board.get_own_num(color.clone(), player_id), own_num);
+            }
+
+            let most_expensive = board
+                .places
+                .iter()
+                .filter(|place| place.get_owner() != Some(player_id))
+                .map(|place| place.get_rent(&fresh).unwrap_or(0))
+                .max()
+                .unwrap_or(0);
+            assert_eq!(board.get_most_expensive(player_id), most_expensive);
+        }
+
+        for color in colors {
+            let owners = board
+                .places
+                .iter()
+                .filter(|place| place.get_color() == color)
+                .map(|place| place.get_owner())
+                .collect::<Vec<_>>();
+            let monopolizer = owners[0].filter(|&owner| owners.iter().all(|&o| o == Some(owner)));
+            assert_eq!(board.get_monopolizer(color), monopolizer);
+        }
+    }
+
+    ///
+    /// Gets ids of the places which can be owned.
+    ///
+    fn get_properties(board: &Board) -> Vec<usize> {
+        board
+            .places
+            .iter()
+            .filter(|place| place.is_property())
+            .map(|place| place.get_id())
+            .collect()
+    }
+
+    ///
+    /// Generates a board where every color is monopolized by one of the players.
+    ///
+    fn get_monopolized_board() -> Board {
+        let mut board = Board::new();
+        for id in get_properties(&board) {
+            let owner = board.places[id].get_color() as usize % PLAYER_NUM;
+            board.set_owner(id, Some(owner));
+        }
+        board
+    }
+
+    #[test]
+    fn index_matches_scan_after_buying() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut board = Board::new();
+        assert_indexed(&board);
+
+        // Half of the colors go to a single player, and the others are scattered.
+        for id in get_properties(&board) {
+            let color = board.places[id].get_color() as usize;
+            let owner = if color.is_multiple_of(2) {
+                color % PLAYER_NUM
+            } else {
+                rng.gen_range(0..PLAYER_NUM)
+            };
+            board.set_owner(id, Some(owner));
+            assert_indexed(&board);
+        }
+
+        // The places change hands through trades.
+        let properties = get_properties(&board);
+        for _ in 0..100 {
+            let id = properties[rng.gen_range(0..properties.len())];
+            board.set_owner(id, Some(rng.gen_range(0..PLAYER_NUM)));
+            assert_indexed(&board);
+        }
+    }
+
+    #[test]
+    fn index_matches_scan_after_mortgaging() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut board = get_monopolized_board();
+        let properties = get_properties(&board);
+
+        for _ in 0..200 {
+            let id = properties[rng.gen_range(0..properties.len())];
+            let place = &board.places[id];
+            if place.is_estate() && !place.is_mortgaged() && rng.gen_bool(0.5) {
+                board.set_num_houses(id, rng.gen_range(0..=5));
+            } else if place.get_num_houses().unwrap_or(0) == 0 {
+                let mortgaged = !place.is_mortgaged();
+                board.set_mortgaged(id, mortgaged);
+            }
+            assert_indexed(&board);
+        }
+    }
+
+    #[test]
+    fn index_matches_scan_after_foreclosure() {
+        // The player bankrupted has the most expensive rents, which have to be forgotten.
+        let mut board = get_monopolized_board();
+        for id in board.get_owned_places(1).to_vec() {
+            if board.places[id].is_estate() {
+                board.set_num_houses(id, 3);
+            }
+        }
+        assert_indexed(&board);
+
+        // The properties of a player bankrupted by the bank return to it.
+        for id in board.get_owned_places(1).to_vec() {
+            board.set_owner(id, None);
+            board.set_mortgaged(id, false);
+            if board.places[id].is_estate() {
+                board.set_num_houses(id, 0);
+            }
+            assert_indexed(&board);
+        }
+
+        // The properties of a player bankrupted by another player are inherited.
+        for id in board.get_owned_places(2).to_vec() {
+            board.set_owner(id, Some(3));
+            assert_indexed(&board);
+        }
+        assert!(board.get_owned_places(2).is_empty());
+    }
+
+    #[test]
+    fn index_matches_scan_after_from_infos() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..50 {
+            let board = Board::new();
+            let infos = get_properties(&board)
+                .into_iter()
+                .map(|id| {
+                    let color = board.places[id].get_color() as usize;
+                    let owner = match rng.gen_range(0..3) {
+                        0 => None,
+                        1 => Some(color % PLAYER_NUM),
+                        _ => Some(rng.gen_range(0..PLAYER_NUM)),
+                    };
+                    let houses = if board.places[id].is_estate() && owner.is_some() {
+                        Some(rng.gen_range(0..=5))
+                    } else {
+                        None
+                    };
+                    let is_mortgaged = owner.is_some() && houses.unwrap_or(0) == 0 && rng.gen();
+                    PlaceInfo {
+                        place_id: id,
+                        owner,
+                        is_mortgaged,
+                        houses,
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            assert_indexed(&Board::from_infos(&infos));
         }
-        most_expensive
     }
 }
@@ -1,11 +1,21 @@
 use std::error::Error;
 use std::fs::File;
-use std::io::{Read, Write};
+use std::io::{stdout, Read, Write};
 use std::path::Path;
 
 use serde::{Deserialize, Serialize};
 
-use crate::board::GameSession;
+use crate::action::Decision;
+use crate::advisor::AdvisorParams;
+use crate::board::{game_log, GameSession};
+use crate::ledger::{Party, TransferReason};
+use crate::places::BoardPlace;
+use crate::player::PlayerState;
+use crate::replay::Replay;
+use crate::simulation::{ObserverFactory, Simulation};
+use crate::statistics::GameStatistics;
+use crate::tournament::{Tournament, TournamentParams};
+use crate::tuning::{Tuner, TuningCheckpoint, TuningParams};
 
 ///
 /// Holds arguments of analysis command.
@@ -15,6 +25,8 @@ pub struct AnalysisCommandArg {
     pub file_name: String,
     pub iteration: i32,
     pub simulation_turn: usize,
+    #[serde(default)]
+    pub statistics_file: Option<String>,
 }
 
 impl std::fmt::Display for AnalysisCommandArg {
@@ -29,6 +41,9 @@ impl std::fmt::Display for AnalysisCommandArg {
 pub enum GameCommand<'a> {
     Init(u32, &'a mut Option<GameSession>),
     Step(u32, &'a mut GameSession),
+    Undo(&'a mut GameSession),
+    Redo(&'a mut GameSession),
+    Goto(usize, &'a mut GameSession),
     ModifyMoney {
         player_id: usize,
         money: i32,
@@ -40,8 +55,22 @@ pub enum GameCommand<'a> {
         session: &'a mut GameSession,
     },
     Save(&'a str, &'a GameSession),
+    SaveLedger(&'a str, &'a GameSession),
+    Audit(bool, &'a mut GameSession),
+    Record(&'a mut GameSession),
+    SaveReplay(&'a str, &'a GameSession),
+    LoadReplay(&'a str, &'a mut Option<Replay>, &'a mut Option<GameSession>),
+    GotoReplay(usize, &'a Replay, &'a mut Option<GameSession>),
+    VerifyReplay(&'a str),
     Load(&'a str, &'a mut Option<GameSession>),
-    Analyze(AnalysisCommandArg, &'a GameSession),
+    Advise(usize, u32, &'a GameSession),
+    Tournament(TournamentParams),
+    Tune {
+        generations: u32,
+        checkpoint_file: &'a str,
+        output_file: &'a str,
+    },
+    Analyze(AnalysisCommandArg, &'a GameSession, Vec<ObserverFactory>),
 }
 
 impl<'a> GameCommand<'a> {
@@ -56,6 +85,25 @@ impl<'a> GameCommand<'a> {
             Self::Step(step, session) => {
                 for _ in 0..*step {
                     session.spend_one_turn();
+
+                    if session.should_stop() {
+                        break;
+                    }
+                }
+            }
+            Self::Undo(session) => {
+                if !session.undo() {
+                    println!("There is nothing to undo.")
+                }
+            }
+            Self::Redo(session) => {
+                if !session.redo() {
+                    println!("There is nothing to redo.")
+                }
+            }
+            Self::Goto(turn, session) => {
+                if !session.goto(*turn) {
+                    println!("Turn {} is out of the history.", turn)
                 }
             }
             Self::ModifyMoney {
@@ -63,13 +111,32 @@ impl<'a> GameCommand<'a> {
                 money,
                 session,
             } => {
-                let player = session.get_player_mut(*player_id);
+                session.save_to_history();
+
+                let player = session.get_player(*player_id);
                 let modified = player.money as i32 + *money;
                 if modified < 0 {
-                    session
-                        .logs
-                        .push(format!("[PLAYER{}] Failed to pay ${}.", player_id, -*money))
+                    game_log!(
+                        session,
+                        Summary,
+                        "[PLAYER{}] Failed to pay ${}.",
+                        player_id,
+                        -*money
+                    )
                 } else {
+                    let (payer, payee) = if *money < 0 {
+                        (Party::Player(*player_id), Party::Bank)
+                    } else {
+                        (Party::Bank, Party::Player(*player_id))
+                    };
+                    session.record_transfer(
+                        payer,
+                        payee,
+                        money.unsigned_abs(),
+                        TransferReason::Adjustment,
+                    );
+
+                    let player = session.get_player_mut(*player_id);
                     player.money = modified as u32;
                 }
             }
@@ -78,19 +145,165 @@ impl<'a> GameCommand<'a> {
                 place_id,
                 session,
             } => {
-                session.board.places[*place_id].set_owner(Some(*player_id));
+                session.save_to_history();
+                session.board.set_owner(*place_id, Some(*player_id));
 
                 let place_name = session.board.places[*place_id].get_place_name();
-                session.logs.push(format!(
+                game_log!(
+                    session,
+                    Summary,
                     "[PLAYER{}] Become an owner of {}.",
-                    player_id, place_name
-                ))
+                    player_id,
+                    place_name
+                )
             }
             Self::Save(file_name, session) => {
                 let json = session.to_json();
                 let mut f = File::create(file_name)?;
                 f.write_all(json.as_bytes())?;
             }
+            Self::SaveLedger(file_name, session) => {
+                let extension = Path::new(&file_name).extension().unwrap().to_str().unwrap();
+                let text = match extension {
+                    "json" => session.ledger.to_json(),
+                    _ => session.ledger.to_csv(),
+                };
+                let mut f = File::create(file_name)?;
+                f.write_all(text.as_bytes())?;
+            }
+            Self::Audit(enabled, session) => {
+                session.set_auditing(*enabled);
+            }
+            Self::Record(session) => {
+                session.start_recording();
+            }
+            Self::SaveReplay(file_name, session) => {
+                if let Some(replay) = session.get_replay() {
+                    let mut f = File::create(file_name)?;
+                    f.write_all(replay.to_json().as_bytes())?;
+                } else {
+                    println!("The game is not being recorded.")
+                }
+            }
+            Self::LoadReplay(file_name, replay, session) => {
+                let mut f = File::open(file_name)?;
+                let mut json = String::new();
+                f.read_to_string(&mut json)?;
+
+                let loaded = Replay::from_json(&json)?;
+                **session = Some(GameSession::from_replay(&loaded)?);
+                **replay = Some(loaded);
+            }
+            Self::GotoReplay(turn, replay, session) => {
+                **session = Some(replay.goto(*turn)?);
+            }
+            Self::VerifyReplay(file_name) => {
+                let mut f = File::open(file_name)?;
+                let mut json = String::new();
+                f.read_to_string(&mut json)?;
+
+                match Replay::from_json(&json)?.verify() {
+                    Ok(_) => println!("The replay reproduces the same game."),
+                    Err(msg) => println!("The replay diverges. {}", msg),
+                }
+            }
+            Self::Advise(player_id, iterations, session) => {
+                if *player_id >= session.players.len() {
+                    println!("The player does not exist.");
+                    return Ok(());
+                }
+
+                // The current player in the jail decides whether to leave it before rolling dices.
+                let player = session.get_player(*player_id);
+                let decision = match player.state {
+                    PlayerState::InJail(_) if session.turn == *player_id => Decision::LeaveJail,
+                    _ => Decision::Invest,
+                };
+                let params = AdvisorParams {
+                    iterations: *iterations,
+                    ..Default::default()
+                };
+
+                println!("{:?}", decision);
+                for ranked in session.get_advice(*player_id, &decision, &params)? {
+                    let (lower, upper) = ranked.confidence_interval;
+                    println!(
+                        "{:>5.1}% ({:.1}% - {:.1}%, {} rollouts) {:?}",
+                        ranked.win_rate * 100.0,
+                        lower * 100.0,
+                        upper * 100.0,
+                        ranked.visits,
+                        ranked.actions
+                    );
+                }
+            }
+            Self::Tournament(params) => match Tournament::new(params.clone()).run() {
+                Ok(result) => {
+                    println!(
+                        "{} games, {:.1} turns on average",
+                        result.games, result.average_length
+                    );
+                    for entrant in result.entrants {
+                        let (lower, upper) = entrant.win_rate_interval;
+                        println!(
+                            "{:>5.1}% ({:.1}% - {:.1}%) position {:.2} rating {:.0} {}",
+                            entrant.win_rate * 100.0,
+                            lower * 100.0,
+                            upper * 100.0,
+                            entrant.average_position,
+                            entrant.rating,
+                            entrant.strategy.name
+                        );
+                    }
+                }
+                Err(msg) => println!("{}", msg),
+            },
+            Self::Tune {
+                generations,
+                checkpoint_file,
+                output_file,
+            } => {
+                // Resumes the tuning if the checkpoint exists.
+                let tuner = match File::open(*checkpoint_file) {
+                    Ok(mut f) => {
+                        let mut json = String::new();
+                        f.read_to_string(&mut json)?;
+                        TuningCheckpoint::from_json(&json).and_then(Tuner::from_checkpoint)
+                    }
+                    Err(_) => Tuner::new(TuningParams::default()),
+                };
+                let mut tuner = match tuner {
+                    Ok(tuner) => tuner,
+                    Err(msg) => {
+                        println!("{}", msg);
+                        return Ok(());
+                    }
+                };
+
+                for _ in 0..*generations {
+                    if let Err(msg) = tuner.step() {
+                        println!("{}", msg);
+                        return Ok(());
+                    }
+
+                    let checkpoint = tuner.get_checkpoint();
+                    let (best, mean) = checkpoint.history.last().unwrap();
+                    println!(
+                        "Generation {}: best {:.1}%, mean {:.1}%",
+                        checkpoint.generation,
+                        best * 100.0,
+                        mean * 100.0
+                    );
+
+                    let mut f = File::create(*checkpoint_file)?;
+                    f.write_all(checkpoint.to_json().as_bytes())?;
+                }
+
+                if let Some(info) = tuner.get_best_info() {
+                    let mut f = File::create(*output_file)?;
+                    f.write_all(info.to_json().as_bytes())?;
+                }
+            }
             Self::Load(file_name, session) => {
                 let extension = Path::new(&file_name).extension().unwrap().to_str().unwrap();
                 match extension {
@@ -99,40 +312,57 @@ impl<'a> GameCommand<'a> {
                         let mut json = String::new();
                         f.read_to_string(&mut json)?;
 
-                        **session = Some(GameSession::from_json(&json));
+                        **session = Some(GameSession::from_json(&json)?);
                     }
                     "xlsx" => {
-                        **session = Some(GameSession::from_excel(file_name));
+                        **session = Some(GameSession::from_excel(file_name)?);
                     }
                     ext => {
                         println!("Files with \".{}\" are not supported.", ext)
                     }
                 }
             }
-            Self::Analyze(arg, session) => {
+            Self::Analyze(arg, session, factories) => {
                 let mut result = String::new();
                 result += "turn,player,money,tap\n";
 
-                let json = session.to_json();
-                for _ in 0..arg.iteration {
-                    let mut game = GameSession::from_json(&json);
-                    for i in 0..arg.simulation_turn {
-                        game.spend_one_turn();
-
-                        let summaries = game.export_summaries(i);
-                        let summaries = summaries
-                            .iter()
-                            .map(|summary| summary.to_string())
-                            .collect::<Vec<_>>()
-                            .join("\n");
-
-                        result += &summaries;
-                        result += "\n";
-                    }
+                let mut simulation = Simulation::from_session(
+                    session,
+                    arg.iteration.max(0) as u32,
+                    arg.simulation_turn,
+                );
+                for factory in factories.drain(..) {
+                    simulation.attach(factory);
+                }
+                simulation.set_progress(Box::new(|completed, total| {
+                    print!("\r{}/{} games are simulated.", completed, total);
+                    let _ = stdout().flush();
+                }));
+
+                let games = simulation.run(
+                    |(summaries, _): &mut (String, GameStatistics), i, game| {
+                        for summary in game.export_summaries(i) {
+                            *summaries += &summary.to_string();
+                            *summaries += "\n";
+                        }
+                    },
+                    |(_, statistics), game| *statistics = game.statistics.clone(),
+                );
+                println!();
+
+                let mut statistics = GameStatistics::default();
+                for (summaries, game_statistics) in &games {
+                    result += summaries;
+                    statistics.merge(game_statistics);
                 }
 
                 let mut f = File::create(&arg.file_name)?;
                 f.write_all(result.as_bytes())?;
+
+                if let Some(statistics_file) = &arg.statistics_file {
+                    let mut f = File::create(statistics_file)?;
+                    f.write_all(statistics.to_json().as_bytes())?;
+                }
             }
         }
 
@@ -1,4 +1,5 @@
-use rand::{thread_rng, Rng};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 ///
 /// Holds a result of dice-rolling.
@@ -9,6 +10,17 @@ pub enum DiceResult {
 }
 
 impl DiceResult {
+    ///
+    /// Generates a result from the faces of the dices.
+    ///
+    pub fn from_faces((first, second): (u32, u32)) -> Self {
+        if first == second {
+            DiceResult::Same(first + second)
+        } else {
+            DiceResult::Different(first + second)
+        }
+    }
+
     ///
     /// Unwraps the result.
     ///
@@ -23,20 +35,75 @@ impl DiceResult {
 ///
 /// Has a function of rolling dices.
 ///
-pub struct DiceRolling;
+/// The dices are driven by a seeded RNG, so that a game can be reproduced from the seed.
+/// Scripted faces, if any, take precedence over the RNG.
+/// The RNG advances on every roll even then, so that the rolls after the script are the same as the original game.
+///
+#[derive(Clone)]
+pub struct DiceRolling {
+    rng: StdRng,
+    scripted: Vec<(u32, u32)>,
+    next: usize,
+}
+
+///
+/// Holds where the dices are, which can be restored without copying the scripted faces.
+///
+#[derive(Clone)]
+pub(crate) struct DiceState {
+    rng: StdRng,
+    next: usize,
+}
 
 impl DiceRolling {
     ///
-    /// Rolls dices.
+    /// Generates dices with the seed.
     ///
-    pub fn roll() -> DiceResult {
-        let mut thread = thread_rng();
-        let first = thread.gen_range(1..(6 + 1));
-        let second = thread.gen_range(1..(6 + 1));
-        if first == second {
-            DiceResult::Same(first + second)
-        } else {
-            DiceResult::Different(first + second)
+    pub fn new(seed: u64) -> Self {
+        DiceRolling {
+            rng: StdRng::seed_from_u64(seed),
+            scripted: Vec::new(),
+            next: 0,
         }
     }
+
+    ///
+    /// Makes the dices show the designated faces in order before using the RNG.
+    ///
+    pub fn script<I: IntoIterator<Item = (u32, u32)>>(&mut self, faces: I) {
+        self.scripted.extend(faces);
+    }
+
+    ///
+    /// Rolls dices and returns their faces.
+    ///
+    pub fn roll(&mut self) -> (u32, u32) {
+        let first = self.rng.gen_range(1..(6 + 1));
+        let second = self.rng.gen_range(1..(6 + 1));
+        match self.scripted.get(self.next) {
+            Some(&faces) => {
+                self.next += 1;
+                faces
+            }
+            None => (first, second),
+        }
+    }
+
+    ///
+    /// Gets the state of the RNG and how many scripted faces have been shown.
+    ///
+    pub(crate) fn get_state(&self) -> DiceState {
+        DiceState {
+            rng: self.rng.clone(),
+            next: self.next,
+        }
+    }
+
+    ///
+    /// Restores the state got by `get_state`.
+    ///
+    pub(crate) fn set_state(&mut self, state: DiceState) {
+        self.rng = state.rng;
+        self.next = state.next;
+    }
 }
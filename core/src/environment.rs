@@ -0,0 +1,403 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{spawn, JoinHandle};
+
+use serde::{Deserialize, Serialize};
+
+use crate::action::Decision;
+use crate::advisor::get_score;
+use crate::board::{GameSession, LogLevel};
+use crate::observer::{StrategyAction, TradeOffer};
+use crate::places::BoardPlace;
+use crate::player::PlayerState;
+use crate::serialization::StrategyInfo;
+use crate::strategy::{
+    ExpensiveHousesProtectionStrategy, GameView, PlayerStrategy, StrategyRegistry,
+};
+
+///
+/// Holds the parameters of `Environment`.
+///
+/// The seat `agent` is driven through the environment, and the others are played by `opponents`.
+/// An episode ends when the agent is bankrupted, only one player survives, or `max_turns` turns are played.
+///
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnvironmentParams {
+    pub player_num: u32,
+    pub agent: usize,
+    pub opponents: StrategyInfo,
+    pub max_turns: usize,
+}
+
+impl Default for EnvironmentParams {
+    fn default() -> Self {
+        EnvironmentParams {
+            player_num: 4,
+            agent: 0,
+            opponents: StrategyInfo::new(ExpensiveHousesProtectionStrategy::NAME),
+            max_turns: 1000,
+        }
+    }
+}
+
+///
+/// Holds a result of a step of the environment.
+///
+/// `reward` is given only at the end of an episode:
+/// -1 if the agent is bankrupted, 1 if they win (shared among the players tied), and 0 otherwise.
+///
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StepResult {
+    pub observation: Vec<f32>,
+    pub reward: f64,
+    pub done: bool,
+}
+
+///
+/// A message sent from the game to the environment.
+///
+/// Each of `observations` is the observation of the player with the same index.
+///
+enum Message {
+    Decide {
+        decision: Decision,
+        observations: Vec<Vec<f32>>,
+        legal_actions: Vec<StrategyAction>,
+    },
+    Finish {
+        observations: Vec<Vec<f32>>,
+        reward: f64,
+    },
+}
+
+///
+/// An environment for reinforcement learning, where the agent plays one of the seats.
+///
+/// The game runs on another thread, which waits for the agent on every decision of the seat.
+///
+pub struct Environment {
+    params: EnvironmentParams,
+    messages: Option<Receiver<Message>>,
+    responses: Option<Sender<Vec<StrategyAction>>>,
+    game: Option<JoinHandle<()>>,
+    current: Option<Message>,
+}
+
+impl Environment {
+    ///
+    /// Generates an environment.
+    ///
+    /// Fails if the seat of the agent does not exist or the strategy of the opponents cannot be constructed.
+    ///
+    pub fn new(params: EnvironmentParams) -> Result<Self, String> {
+        if params.agent >= params.player_num as usize {
+            return Err(format!("PLAYER{} does not exist.", params.agent));
+        }
+        StrategyRegistry::get_installed().create(&params.opponents)?;
+
+        Ok(Environment {
+            params,
+            messages: None,
+            responses: None,
+            game: None,
+            current: None,
+        })
+    }
+
+    ///
+    /// Starts a new episode with the seed, and returns the observation of the agent.
+    ///
+    pub fn reset(&mut self, seed: u64) -> Vec<f32> {
+        self.stop();
+
+        let (message_sender, messages) = channel();
+        let (response_sender, responses) = channel();
+        let abandoned = Arc::new(AtomicBool::new(false));
+
+        let params = self.params.clone();
+        let agent = AgentStrategy {
+            messages: message_sender.clone(),
+            responses,
+            abandoned: abandoned.clone(),
+            info: params.opponents.clone(),
+        };
+        self.game = Some(spawn(move || {
+            let mut game = GameSession::new(params.player_num);
+            game.set_seed(seed);
+            game.set_log_level(LogLevel::Off);
+            for player_id in 0..params.player_num as usize {
+                let strategy = StrategyRegistry::get_installed()
+                    .create(&params.opponents)
+                    .unwrap();
+                game.get_player_mut(player_id).set_strategy(strategy);
+            }
+            game.get_player_mut(params.agent)
+                .set_strategy(Box::new(agent));
+
+            for _ in 0..params.max_turns {
+                if game.get_player(params.agent).is_bankrupted()
+                    || game.count_active_players() <= 1
+                    || abandoned.load(Ordering::Relaxed)
+                {
+                    break;
+                }
+                game.spend_one_turn();
+            }
+
+            let reward = if game.get_player(params.agent).is_bankrupted() {
+                -1.0
+            } else {
+                get_score(&game, params.agent)
+            };
+            let _ = message_sender.send(Message::Finish {
+                observations: get_observations(&game, None),
+                reward,
+            });
+        }));
+        self.messages = Some(messages);
+        self.responses = Some(response_sender);
+
+        self.receive();
+        self.observe(self.params.agent)
+    }
+
+    ///
+    /// Gets the observation of the player.
+    ///
+    /// The vector has the same length throughout the episodes with the same number of players.
+    /// The players in it are ordered from the player themselves in the order of turns.
+    ///
+    pub fn observe(&self, player_id: usize) -> Vec<f32> {
+        match self
+            .current
+            .as_ref()
+            .expect("The environment is not reset.")
+        {
+            Message::Decide { observations, .. } | Message::Finish { observations, .. } => {
+                observations[player_id].clone()
+            }
+        }
+    }
+
+    ///
+    /// Gets the decision which the agent is asked to make, or `None` if the episode is done.
+    ///
+    pub fn get_decision(&self) -> Option<&Decision> {
+        match self.current.as_ref() {
+            Some(Message::Decide { decision, .. }) => Some(decision),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Enumerates the actions which the agent can take on the current decision.
+    ///
+    pub fn legal_actions(&self) -> Vec<StrategyAction> {
+        match self.current.as_ref() {
+            Some(Message::Decide { legal_actions, .. }) => legal_actions.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    ///
+    /// Takes the actions on the current decision, and proceeds the game to the next decision of the agent.
+    ///
+    /// The actions are applied in order as the response of a strategy, so an empty vector means passing.
+    ///
+    pub fn step(&mut self, actions: Vec<StrategyAction>) -> StepResult {
+        assert!(
+            self.get_decision().is_some(),
+            "The episode is done or not started."
+        );
+
+        // The game has already finished if it refuses the response.
+        if self.responses.as_ref().unwrap().send(actions).is_ok() {
+            self.receive();
+        }
+
+        let observation = self.observe(self.params.agent);
+        match self.current.as_ref().unwrap() {
+            Message::Decide { .. } => StepResult {
+                observation,
+                reward: 0.0,
+                done: false,
+            },
+            &Message::Finish { reward, .. } => StepResult {
+                observation,
+                reward,
+                done: true,
+            },
+        }
+    }
+
+    ///
+    /// Waits for the next message from the game.
+    ///
+    fn receive(&mut self) {
+        self.current = Some(self.messages.as_ref().unwrap().recv().unwrap());
+    }
+
+    ///
+    /// Abandons the episode in progress and waits for the game to finish.
+    ///
+    fn stop(&mut self) {
+        // Dropping the channels lets the agent on the game thread give up.
+        self.responses = None;
+        self.messages = None;
+        self.current = None;
+        if let Some(game) = self.game.take() {
+            game.join().unwrap();
+        }
+    }
+}
+
+impl Drop for Environment {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+///
+/// A strategy which asks the environment for decisions.
+///
+/// Once the environment is gone, it passes on every decision and lets the game stop.
+///
+struct AgentStrategy {
+    messages: Sender<Message>,
+    responses: Receiver<Vec<StrategyAction>>,
+    abandoned: Arc<AtomicBool>,
+    info: StrategyInfo,
+}
+
+impl AgentStrategy {
+    fn ask(&self, view: &GameView, decision: Decision) -> Vec<StrategyAction> {
+        if self.abandoned.load(Ordering::Relaxed) {
+            return Vec::new();
+        }
+
+        let message = Message::Decide {
+            legal_actions: view.get_legal_actions(&decision),
+            observations: (0..view.players.len())
+                .map(|player_id| {
+                    let view = GameView { player_id, ..*view };
+                    get_observation(&view, Some(&decision))
+                })
+                .collect(),
+            decision,
+        };
+        let actions = self
+            .messages
+            .send(message)
+            .ok()
+            .and_then(|_| self.responses.recv().ok());
+        if actions.is_none() {
+            self.abandoned.store(true, Ordering::Relaxed);
+        }
+        actions.unwrap_or_default()
+    }
+}
+
+impl PlayerStrategy for AgentStrategy {
+    fn raise(&self, view: &GameView, debt: u32) -> Vec<StrategyAction> {
+        self.ask(view, Decision::Raise(debt))
+    }
+
+    fn invest(&self, view: &GameView) -> Vec<StrategyAction> {
+        self.ask(view, Decision::Invest)
+    }
+
+    fn buy(&self, view: &GameView, place_id: usize, price: u32) -> Vec<StrategyAction> {
+        self.ask(view, Decision::Buy(place_id, price))
+    }
+
+    fn leave_jail(&self, view: &GameView) -> Vec<StrategyAction> {
+        self.ask(view, Decision::LeaveJail)
+    }
+
+    fn accepts_trade(&self, _view: &GameView, _proposer: usize, _offer: &TradeOffer) -> bool {
+        false
+    }
+
+    fn get_info(&self) -> StrategyInfo {
+        // Serialized games fall back to the strategy of the opponents on the seat.
+        self.info.clone()
+    }
+
+    fn clone_boxed(&self) -> Box<dyn PlayerStrategy + Send> {
+        // The environment drives only the original seat, so the clone plays as one of the opponents.
+        StrategyRegistry::get_installed()
+            .create(&self.info)
+            .unwrap()
+    }
+}
+
+///
+/// Gets the observations of all the players out of any decisions.
+///
+fn get_observations(game: &GameSession, decision: Option<&Decision>) -> Vec<Vec<f32>> {
+    (0..game.players.len())
+        .map(|player_id| get_observation(&game.get_view(player_id), decision))
+        .collect()
+}
+
+///
+/// Encodes the game seen by the player into a vector of features.
+///
+/// The features are, for each player, the position in one-hot, the money in thousands of dollars,
+/// whether they are in the jail, the turns spent in the jail and whether they are bankrupted;
+/// for each place, the owner in one-hot, the houses over 5 and whether it is mortgaged;
+/// and the kind of the decision in one-hot with the debt or the price in thousands of dollars.
+///
+pub fn get_observation(view: &GameView, decision: Option<&Decision>) -> Vec<f32> {
+    let player_num = view.players.len();
+    let place_num = view.board.places.len();
+    let mut features = Vec::new();
+
+    for offset in 0..player_num {
+        let player = &view.players[(view.player_id + offset) % player_num];
+
+        let mut position = vec![0.0; place_num];
+        position[player.position] = 1.0;
+        features.extend(position);
+
+        features.push(player.money as f32 / 1000.0);
+        match player.state {
+            PlayerState::InJail(turns) => features.extend([1.0, turns as f32 / 3.0, 0.0]),
+            PlayerState::Bankrupted => features.extend([0.0, 0.0, 1.0]),
+            PlayerState::None => features.extend([0.0, 0.0, 0.0]),
+        }
+    }
+
+    for place in &view.board.places {
+        let mut owner = vec![0.0; player_num];
+        if let Some(owner_id) = place.get_owner() {
+            owner[(owner_id + player_num - view.player_id) % player_num] = 1.0;
+        }
+        features.extend(owner);
+
+        features.push(place.get_num_houses().unwrap_or(0) as f32 / 5.0);
+        features.push(if place.is_mortgaged() { 1.0 } else { 0.0 });
+    }
+
+    let mut kind = [0.0; 4];
+    let mut amount = 0.0;
+    match decision {
+        Some(&Decision::Raise(debt)) => {
+            kind[0] = 1.0;
+            amount = debt as f32 / 1000.0;
+        }
+        Some(Decision::Invest) => kind[1] = 1.0,
+        Some(&Decision::Buy(_, price)) => {
+            kind[2] = 1.0;
+            amount = price as f32 / 1000.0;
+        }
+        Some(Decision::LeaveJail) => kind[3] = 1.0,
+        None => {}
+    }
+    features.extend(kind);
+    features.push(amount);
+
+    features
+}
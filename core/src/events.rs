@@ -17,6 +17,11 @@ pub enum EventKind<'a> {
     /// The third argument is the amount of dollars to pay.
     PayToOther(&'a str, usize, u32),
 
+    /// Must pay dollars to the other player according to the dices.
+    ///
+    /// The third argument is the multiplier of the sum of the dices.
+    PayToOtherByDice(&'a str, usize, u32),
+
     ///
     /// Get a profit.
     ///
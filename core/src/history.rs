@@ -0,0 +1,274 @@
+use std::collections::VecDeque;
+
+use crate::board::{game_log, Board, GameSession};
+use crate::dice_rolling::DiceState;
+use crate::ledger::Transfer;
+use crate::replay::ReplayTurn;
+use crate::serialization::GameInfo;
+use crate::simulation::get_strategy_seed;
+use crate::statistics::GameStatistics;
+use crate::strategy::PlayerStrategy;
+
+///
+/// A state of a game session which can be restored.
+///
+/// Logs are not included, since they record what has happened including undoing.
+/// The ledger and the recording only grow, so they are kept as their lengths and truncated on restoring.
+/// A snapshot ahead of the session, which is taken on undoing, keeps the entries dropped as well to redo.
+///
+/// The strategies are kept as their clones. Note that an external bot cannot be cloned while running,
+/// so it is restarted on restoring and loses what it has learned in the game.
+///
+#[derive(Clone)]
+pub struct Snapshot {
+    pub info: GameInfo,
+    pub turn_count: usize,
+    ledger_len: usize,
+    ledger_tail: Vec<Transfer>,
+    statistics: GameStatistics,
+    seed: u64,
+    dice: DiceState,
+    recording_len: Option<usize>,
+    recording_tail: Vec<ReplayTurn>,
+    strategies: Vec<Box<dyn PlayerStrategy + Send>>,
+}
+
+///
+/// Holds a bounded history of snapshots to undo or redo turns.
+///
+#[derive(Default, Clone)]
+pub struct History {
+    capacity: usize,
+    past: VecDeque<Snapshot>,
+    future: Vec<Snapshot>,
+}
+
+impl History {
+    ///
+    /// Generates a history which keeps at most `capacity` snapshots.
+    ///
+    /// If `capacity` is zero, the history is disabled.
+    ///
+    pub fn new(capacity: usize) -> Self {
+        History {
+            capacity,
+            past: VecDeque::new(),
+            future: Vec::new(),
+        }
+    }
+
+    ///
+    /// Gets whether the history keeps snapshots.
+    ///
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+}
+
+impl GameSession {
+    ///
+    /// Sets how many snapshots the session keeps for undoing.
+    ///
+    /// Giving zero disables the history.
+    ///
+    pub fn set_history_capacity(&mut self, capacity: usize) {
+        self.history.capacity = capacity;
+        while self.history.past.len() > capacity {
+            self.history.past.pop_front();
+        }
+        if capacity == 0 {
+            self.history.future.clear();
+        }
+    }
+
+    ///
+    /// Takes a snapshot of the current state.
+    ///
+    pub fn take_session_snapshot(&self) -> Snapshot {
+        Snapshot {
+            info: self.to_info(),
+            turn_count: self.turn_count,
+            ledger_len: self.ledger.get_transfers().len(),
+            ledger_tail: Vec::new(),
+            statistics: self.statistics.clone(),
+            seed: self.seed,
+            dice: self.dice.get_state(),
+            recording_len: self
+                .recording
+                .as_ref()
+                .map(|recording| recording.turns.len()),
+            recording_tail: Vec::new(),
+            strategies: self
+                .players
+                .iter()
+                .map(|player| player.get_strategy().clone_boxed())
+                .collect(),
+        }
+    }
+
+    ///
+    /// Takes a snapshot of the current state, keeping the entries which restoring the older one drops.
+    ///
+    fn take_session_snapshot_since(&self, older: &Snapshot) -> Snapshot {
+        let mut snapshot = self.take_session_snapshot();
+        let ledger_start = older.ledger_len.min(snapshot.ledger_len);
+        snapshot.ledger_tail = self.ledger.get_transfers()[ledger_start..].to_vec();
+        if let (Some(recording), Some(len)) = (&self.recording, older.recording_len) {
+            snapshot.recording_tail = recording.turns[len.min(recording.turns.len())..].to_vec();
+        }
+        snapshot
+    }
+
+    ///
+    /// Restores the state from the snapshot, keeping the observers.
+    ///
+    /// The strategies are reseeded from the seed and the turn count of the snapshot,
+    /// so that the game played on from the same state is reproduced.
+    /// The recording is stopped if it had not started at the snapshot.
+    ///
+    pub fn restore_session_snapshot(&mut self, snapshot: Snapshot) {
+        let players = self
+            .players
+            .iter_mut()
+            .zip(&snapshot.info.players)
+            .zip(snapshot.strategies);
+        for ((player, info), strategy) in players {
+            player.set_info(info);
+            strategy.set_seed(get_strategy_seed(
+                snapshot.seed ^ snapshot.turn_count as u64,
+                player.player_id,
+            ));
+            player.set_strategy(strategy);
+        }
+        self.board = Board::from_infos(&snapshot.info.places);
+        self.turn = snapshot.info.turn;
+        self.turn_count = snapshot.turn_count;
+
+        self.ledger
+            .truncate(snapshot.ledger_len - snapshot.ledger_tail.len());
+        for transfer in snapshot.ledger_tail {
+            self.ledger.record(transfer);
+        }
+
+        self.statistics = snapshot.statistics;
+        self.seed = snapshot.seed;
+        self.dice.set_state(snapshot.dice);
+
+        match (&mut self.recording, snapshot.recording_len) {
+            (Some(recording), Some(len)) => {
+                recording
+                    .turns
+                    .truncate(len - snapshot.recording_tail.len());
+                recording.turns.extend(snapshot.recording_tail);
+            }
+            (recording, None) => *recording = None,
+            (None, Some(_)) => {}
+        }
+    }
+
+    ///
+    /// Saves the current state into the history before changing it.
+    ///
+    /// Redoing is no longer available after this.
+    ///
+    pub fn save_to_history(&mut self) {
+        if !self.history.is_enabled() {
+            return;
+        }
+
+        let snapshot = self.take_session_snapshot();
+        self.history.past.push_back(snapshot);
+        if self.history.past.len() > self.history.capacity {
+            self.history.past.pop_front();
+        }
+        self.history.future.clear();
+    }
+
+    ///
+    /// Goes back to the previous state in the history.
+    ///
+    /// Returns `false` if there is nothing to undo.
+    ///
+    pub fn undo(&mut self) -> bool {
+        let Some(snapshot) = self.history.past.pop_back() else {
+            return false;
+        };
+
+        let current = self.take_session_snapshot_since(&snapshot);
+        self.history.future.push(current);
+        self.restore_session_snapshot(snapshot);
+        game_log!(
+            self,
+            Summary,
+            "[HISTORY] Goes back to turn {}.",
+            self.turn_count
+        );
+
+        true
+    }
+
+    ///
+    /// Goes forward to the state undone.
+    ///
+    /// Returns `false` if there is nothing to redo.
+    ///
+    pub fn redo(&mut self) -> bool {
+        let Some(snapshot) = self.history.future.pop() else {
+            return false;
+        };
+
+        let current = self.take_session_snapshot();
+        self.history.past.push_back(current);
+        self.restore_session_snapshot(snapshot);
+        game_log!(
+            self,
+            Summary,
+            "[HISTORY] Goes forward to turn {}.",
+            self.turn_count
+        );
+
+        true
+    }
+
+    ///
+    /// Moves to the state at the designated turn in the history.
+    ///
+    /// Returns `false` if the turn is out of the history. In that case, the state is not changed.
+    ///
+    pub fn goto(&mut self, turn_count: usize) -> bool {
+        let in_past = self
+            .history
+            .past
+            .iter()
+            .any(|snapshot| snapshot.turn_count == turn_count);
+        let in_future = self
+            .history
+            .future
+            .iter()
+            .any(|snapshot| snapshot.turn_count == turn_count);
+
+        if turn_count == self.turn_count {
+            true
+        } else if in_past {
+            while self.turn_count != turn_count && self.undo() {}
+            true
+        } else if in_future {
+            while self.turn_count != turn_count && self.redo() {}
+            true
+        } else {
+            false
+        }
+    }
+
+    ///
+    /// Gets the states in the history followed by the current one.
+    ///
+    pub fn get_history_infos(&self) -> Vec<GameInfo> {
+        self.history
+            .past
+            .iter()
+            .map(|snapshot| snapshot.info.clone())
+            .chain(std::iter::once(self.to_info()))
+            .collect()
+    }
+}
@@ -0,0 +1,264 @@
+use std::fmt::{Display, Error, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+///
+/// A party of a transfer.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Party {
+    Bank,
+    Player(usize),
+}
+
+impl Display for Party {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            Party::Bank => write!(fmt, "BANK"),
+            Party::Player(id) => write!(fmt, "PLAYER{}", id),
+        }
+    }
+}
+
+///
+/// A reason why money is transferred.
+///
+/// Every variant related to a place holds the id of the place.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransferReason {
+    Rent(usize),
+    Purchase(usize),
+    Fee(String),
+    Reward(String),
+    BuildHouse(usize),
+    SellHouse(usize),
+    Mortgage(usize),
+    Unmortgage(usize),
+    Bankruptcy,
+    Foreclosure(usize),
+    Inheritance(usize),
+    Trade,
+    TradePlace(usize),
+    Adjustment,
+}
+
+impl Display for TransferReason {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            TransferReason::Fee(msg) => write!(fmt, "Fee({})", msg),
+            TransferReason::Reward(msg) => write!(fmt, "Reward({})", msg),
+            reason => write!(fmt, "{:?}", reason),
+        }
+    }
+}
+
+///
+/// A record of money moving from a party to another one.
+///
+/// Properties moving without money, such as foreclosures, are recorded as transfers of $0.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transfer {
+    pub turn: usize,
+    pub payer: Party,
+    pub payee: Party,
+    pub amount: u32,
+    pub reason: TransferReason,
+}
+
+impl Display for Transfer {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), Error> {
+        write!(
+            fmt,
+            "{},{},{},{},{}",
+            self.turn, self.payer, self.payee, self.amount, self.reason
+        )
+    }
+}
+
+impl Transfer {
+    ///
+    /// Gets the amount of money the player gains through the transfer.
+    ///
+    /// It can be negative when the player pays.
+    ///
+    pub fn get_gain_of(&self, player_id: usize) -> i64 {
+        let mut gain = 0;
+        if self.payee == Party::Player(player_id) {
+            gain += self.amount as i64;
+        }
+        if self.payer == Party::Player(player_id) {
+            gain -= self.amount as i64;
+        }
+        gain
+    }
+
+    ///
+    /// Gets the amount of money the bank pays to the players through the transfer.
+    ///
+    /// It can be negative when the bank receives.
+    ///
+    pub fn get_bank_flow(&self) -> i64 {
+        match (self.payer, self.payee) {
+            (Party::Bank, Party::Player(_)) => self.amount as i64,
+            (Party::Player(_), Party::Bank) => -(self.amount as i64),
+            _ => 0,
+        }
+    }
+}
+
+///
+/// Records every transfer of money in a game.
+///
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct Ledger {
+    transfers: Vec<Transfer>,
+}
+
+impl Ledger {
+    ///
+    /// Appends a transfer.
+    ///
+    pub fn record(&mut self, transfer: Transfer) {
+        self.transfers.push(transfer);
+    }
+
+    ///
+    /// Gets all of the transfers in the order they were made.
+    ///
+    pub fn get_transfers(&self) -> &[Transfer] {
+        &self.transfers
+    }
+
+    ///
+    /// Drops the transfers after the first `len` ones.
+    ///
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.transfers.truncate(len);
+    }
+
+    ///
+    /// Gets the transfers the player is involved in.
+    ///
+    pub fn get_transfers_of(&self, player_id: usize) -> impl Iterator<Item = &Transfer> {
+        self.transfers.iter().filter(move |transfer| {
+            transfer.payer == Party::Player(player_id) || transfer.payee == Party::Player(player_id)
+        })
+    }
+
+    ///
+    /// Gets the net amount of money the player has gained through the game.
+    ///
+    pub fn get_balance_of(&self, player_id: usize) -> i64 {
+        self.transfers
+            .iter()
+            .map(|transfer| transfer.get_gain_of(player_id))
+            .sum()
+    }
+
+    ///
+    /// Exports the ledger as CSV.
+    ///
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("turn,payer,payee,amount,reason\n");
+        for transfer in &self.transfers {
+            csv += &transfer.to_string();
+            csv += "\n";
+        }
+        csv
+    }
+
+    ///
+    /// Exports the ledger as JSON.
+    ///
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.transfers).unwrap()
+    }
+}
+
+///
+/// Checks that money is conserved through a turn.
+///
+/// The total of money plus asset value of the players is allowed to change only by the flows from or to the bank.
+/// Assets are valued at the price the bank pays for them, i.e. the same way as `Appraiser::get_payable_money`.
+///
+#[derive(Clone)]
+pub struct Auditor {
+    money: Vec<u32>,
+    wealth: i64,
+    expected_change: i64,
+    first_transfer: usize,
+    pub violations: Vec<String>,
+}
+
+impl Auditor {
+    ///
+    /// Generates an auditor.
+    ///
+    pub fn new() -> Self {
+        Auditor {
+            money: Vec::new(),
+            wealth: 0,
+            expected_change: 0,
+            first_transfer: 0,
+            violations: Vec::new(),
+        }
+    }
+
+    ///
+    /// Remembers the state at the beginning of a turn.
+    ///
+    pub fn begin(&mut self, money: Vec<u32>, wealth: i64, ledger: &Ledger) {
+        self.money = money;
+        self.wealth = wealth;
+        self.expected_change = 0;
+        self.first_transfer = ledger.get_transfers().len();
+    }
+
+    ///
+    /// Takes account of a transfer and the change of asset value it brings.
+    ///
+    pub fn expect(&mut self, transfer: &Transfer, asset_change: i64) {
+        self.expected_change += transfer.get_bank_flow() + asset_change;
+    }
+
+    ///
+    /// Checks the state at the end of a turn and returns the violations found.
+    ///
+    pub fn end(&mut self, turn: usize, money: &[u32], wealth: i64, ledger: &Ledger) -> Vec<String> {
+        let mut violations = Vec::new();
+        let transfers = &ledger.get_transfers()[self.first_transfer..];
+
+        for (player_id, (before, after)) in self.money.iter().zip(money).enumerate() {
+            let recorded: i64 = transfers
+                .iter()
+                .map(|transfer| transfer.get_gain_of(player_id))
+                .sum();
+            let actual = *after as i64 - *before as i64;
+            if recorded != actual {
+                violations.push(format!(
+                    "[AUDIT] Turn {}: money of PLAYER{} changed by ${} but the ledger says ${}.",
+                    turn, player_id, actual, recorded
+                ));
+            }
+        }
+
+        let actual = wealth - self.wealth;
+        if actual != self.expected_change {
+            violations.push(format!(
+                "[AUDIT] Turn {}: total wealth changed by ${} but the bank flows explain ${}.",
+                turn, actual, self.expected_change
+            ));
+        }
+
+        self.violations.extend(violations.iter().cloned());
+        violations
+    }
+}
+
+impl Default for Auditor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
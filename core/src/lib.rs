@@ -1,9 +1,20 @@
+pub mod action;
+pub mod advisor;
 pub mod appraiser;
 pub mod board;
 pub mod command;
 mod dice_rolling;
+pub mod environment;
 pub mod events;
+pub mod history;
+pub mod ledger;
+pub mod observer;
 pub mod places;
 pub mod player;
+pub mod replay;
 pub mod serialization;
+pub mod simulation;
+pub mod statistics;
 pub mod strategy;
+pub mod tournament;
+pub mod tuning;
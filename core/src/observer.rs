@@ -0,0 +1,124 @@
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::action::IllegalAction;
+use crate::board::GameSession;
+use crate::events::EventKind;
+use crate::ledger::Transfer;
+
+///
+/// An offer of a trade between two players.
+///
+/// The proposer gives `give` and `give_money` to the partner, and receives `take` and `take_money` from them.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TradeOffer {
+    pub partner: usize,
+    pub give: Vec<usize>,
+    pub take: Vec<usize>,
+    pub give_money: u32,
+    pub take_money: u32,
+}
+
+///
+/// An action which a strategy takes on the game.
+///
+/// Every variant related to a place holds the id of the place.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StrategyAction {
+    BuildHouse(usize),
+    SellHouse(usize),
+    Mortgage(usize),
+    Unmortgage(usize),
+    Buy(usize),
+    Trade(TradeOffer),
+    PayJailFee,
+}
+
+///
+/// Gets notified of what happens in a game.
+///
+/// All of the methods do nothing by default, so implement only the ones you are interested in.
+///
+pub trait GameObserver {
+    ///
+    /// Called when an event is invoked on the player.
+    ///
+    fn on_event(&mut self, _player_id: usize, _event: &EventKind) {}
+
+    ///
+    /// Called when the player moves from a place to another one.
+    ///
+    fn on_move(&mut self, _player_id: usize, _from: usize, _to: usize) {}
+
+    ///
+    /// Called when the strategy of the player takes an action.
+    ///
+    fn on_strategy_action(&mut self, _player_id: usize, _action: &StrategyAction) {}
+
+    ///
+    /// Called when the strategy of the player takes an illegal action, which is not applied.
+    ///
+    fn on_illegal_action(&mut self, _error: &IllegalAction) {}
+
+    ///
+    /// Called when money is transferred.
+    ///
+    fn on_transfer(&mut self, _transfer: &Transfer) {}
+
+    ///
+    /// Called when the player gets bankrupted.
+    ///
+    fn on_bankrupted(&mut self, _player_id: usize) {}
+
+    ///
+    /// Called when a turn comes to the end.
+    ///
+    fn on_turn_end(&mut self, _session: &GameSession) {}
+
+    ///
+    /// Requests the simulation driving the game to stop.
+    ///
+    fn should_stop(&self) -> bool {
+        false
+    }
+}
+
+///
+/// Allows the caller to keep a handle of the observer to collect the result after the game.
+///
+impl<T: GameObserver> GameObserver for Arc<Mutex<T>> {
+    fn on_event(&mut self, player_id: usize, event: &EventKind) {
+        self.lock().unwrap().on_event(player_id, event);
+    }
+
+    fn on_move(&mut self, player_id: usize, from: usize, to: usize) {
+        self.lock().unwrap().on_move(player_id, from, to);
+    }
+
+    fn on_strategy_action(&mut self, player_id: usize, action: &StrategyAction) {
+        self.lock().unwrap().on_strategy_action(player_id, action);
+    }
+
+    fn on_illegal_action(&mut self, error: &IllegalAction) {
+        self.lock().unwrap().on_illegal_action(error);
+    }
+
+    fn on_transfer(&mut self, transfer: &Transfer) {
+        self.lock().unwrap().on_transfer(transfer);
+    }
+
+    fn on_bankrupted(&mut self, player_id: usize) {
+        self.lock().unwrap().on_bankrupted(player_id);
+    }
+
+    fn on_turn_end(&mut self, session: &GameSession) {
+        self.lock().unwrap().on_turn_end(session);
+    }
+
+    fn should_stop(&self) -> bool {
+        self.lock().unwrap().should_stop()
+    }
+}
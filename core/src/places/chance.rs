@@ -1,6 +1,7 @@
 use crate::board::Board;
-use crate::places::{BoardColor, BoardPlace, EventKind};
+use crate::places::{BoardColor, BoardPlace, EventKind, Place};
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Chance {
     id: usize,
 }
@@ -60,7 +61,7 @@ impl BoardPlace for Chance {
 }
 
 impl Chance {
-    pub fn new_boxed(id: usize) -> Box<dyn BoardPlace + Send> {
-        Box::new(Chance { id })
+    pub fn new_place(id: usize) -> Place {
+        Place::Chance(Chance { id })
     }
 }
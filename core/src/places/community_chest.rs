@@ -1,6 +1,7 @@
 use crate::board::Board;
-use crate::places::{BoardColor, BoardPlace, EventKind};
+use crate::places::{BoardColor, BoardPlace, EventKind, Place};
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CommunityChest {
     id: usize,
 }
@@ -60,7 +61,7 @@ impl BoardPlace for CommunityChest {
 }
 
 impl CommunityChest {
-    pub fn new_boxed(id: usize) -> Box<dyn BoardPlace + Send> {
-        Box::new(CommunityChest { id })
+    pub fn new_place(id: usize) -> Place {
+        Place::CommunityChest(CommunityChest { id })
     }
 }
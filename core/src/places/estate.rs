@@ -1,7 +1,8 @@
 use crate::board::Board;
 use crate::events::EventKind;
-use crate::places::{BoardColor, BoardPlace};
+use crate::places::{BoardColor, BoardPlace, Place};
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Estate {
     id: usize,
     color: BoardColor,
@@ -10,7 +11,7 @@ pub struct Estate {
     houses: u8,
     price: u32,
     house_price: u32,
-    rent: Vec<u32>,
+    rent: [u32; 7],
     mortgaged: bool,
 }
 
@@ -88,17 +89,15 @@ impl BoardPlace for Estate {
 }
 
 impl Estate {
-    pub fn new_boxed(
+    pub fn new_place(
         id: usize,
         color: BoardColor,
         name: &'static str,
         price: u32,
         house_price: u32,
-        rent: Vec<u32>,
-    ) -> Box<dyn BoardPlace + Send> {
-        assert_eq!(rent.len(), 7);
-
-        Box::new(Estate {
+        rent: [u32; 7],
+    ) -> Place {
+        Place::Estate(Estate {
             id,
             color,
             name,
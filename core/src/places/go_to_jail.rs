@@ -1,6 +1,7 @@
 use crate::board::Board;
-use crate::places::{BoardColor, BoardPlace, EventKind};
+use crate::places::{BoardColor, BoardPlace, EventKind, Place};
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GoToJail {
     id: usize,
 }
@@ -60,7 +61,7 @@ impl BoardPlace for GoToJail {
 }
 
 impl GoToJail {
-    pub fn new_boxed(id: usize) -> Box<dyn BoardPlace + Send> {
-        Box::new(GoToJail { id })
+    pub fn new_place(id: usize) -> Place {
+        Place::GoToJail(GoToJail { id })
     }
 }
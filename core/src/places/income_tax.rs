@@ -1,6 +1,7 @@
 use crate::board::Board;
-use crate::places::{BoardColor, BoardPlace, EventKind};
+use crate::places::{BoardColor, BoardPlace, EventKind, Place};
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct IncomeTax {
     id: usize,
 }
@@ -60,7 +61,7 @@ impl BoardPlace for IncomeTax {
 }
 
 impl IncomeTax {
-    pub fn new_boxed(id: usize) -> Box<dyn BoardPlace + Send> {
-        Box::new(IncomeTax { id })
+    pub fn new_place(id: usize) -> Place {
+        Place::IncomeTax(IncomeTax { id })
     }
 }
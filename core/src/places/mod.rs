@@ -89,226 +89,322 @@ pub trait BoardPlace {
     fn get_color(&self) -> BoardColor;
     fn is_mortgaged(&self) -> bool;
     fn set_mortgaged(&mut self, mortgaged: bool) -> u32;
-}
 
-impl dyn BoardPlace + Send {
-    pub fn get_return_cost(&self) -> u32 {
+    fn get_return_cost(&self) -> u32 {
         ((self.get_price() / 2) as f32 * 1.1) as u32
     }
 
-    pub fn is_property(&self) -> bool {
+    fn is_property(&self) -> bool {
         self.get_color() != BoardColor::None
     }
 
-    pub fn is_estate(&self) -> bool {
+    fn is_estate(&self) -> bool {
         let color = self.get_color();
         color != BoardColor::None && color != BoardColor::Railroad && color != BoardColor::Utilities
     }
 
-    pub fn get_rent(&self, board: &Board) -> Option<u32> {
+    ///
+    /// Gets the rent of the place.
+    ///
+    /// The rent depending on the dices is estimated with the expectation of the sum of them, 7.
+    ///
+    fn get_rent(&self, board: &Board) -> Option<u32> {
         match self.get_action(usize::MAX, board) {
             EventKind::PayToBank(_, money) => Some(money),
             EventKind::PayToOther(_, _, money) => Some(money),
+            EventKind::PayToOtherByDice(_, _, multiplier) => Some(multiplier * 7),
             _ => None,
         }
     }
 }
 
-pub fn get_place_list() -> Vec<Box<dyn BoardPlace + Send>> {
+///
+/// A place on the board.
+///
+/// Each variant holds the data of the place, so a place can be copied and compared without dynamic dispatch.
+/// It implements `BoardPlace` by delegating to the variant, so it can be used as `dyn BoardPlace` as well.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Place {
+    Chance(Chance),
+    CommunityChest(CommunityChest),
+    Estate(Estate),
+    GoToJail(GoToJail),
+    IncomeTax(IncomeTax),
+    LuxuryTax(LuxuryTax),
+    Nothing(Nothing),
+    Railroad(Railroad),
+    Utilities(Utilities),
+}
+
+///
+/// Calls the method of the place held by the variant.
+///
+macro_rules! dispatch {
+    ($self:expr, $place:ident => $body:expr) => {
+        match $self {
+            Place::Chance($place) => $body,
+            Place::CommunityChest($place) => $body,
+            Place::Estate($place) => $body,
+            Place::GoToJail($place) => $body,
+            Place::IncomeTax($place) => $body,
+            Place::LuxuryTax($place) => $body,
+            Place::Nothing($place) => $body,
+            Place::Railroad($place) => $body,
+            Place::Utilities($place) => $body,
+        }
+    };
+}
+
+impl BoardPlace for Place {
+    fn info(&self) -> String {
+        dispatch!(self, place => place.info())
+    }
+
+    fn get_id(&self) -> usize {
+        dispatch!(self, place => place.get_id())
+    }
+
+    fn get_place_name(&self) -> &'static str {
+        dispatch!(self, place => place.get_place_name())
+    }
+
+    fn get_action<'a>(&self, turn: usize, board: &Board) -> EventKind<'a> {
+        dispatch!(self, place => place.get_action(turn, board))
+    }
+
+    fn get_owner(&self) -> Option<usize> {
+        dispatch!(self, place => place.get_owner())
+    }
+
+    fn set_owner(&mut self, owner: Option<usize>) {
+        dispatch!(self, place => place.set_owner(owner))
+    }
+
+    fn get_num_houses(&self) -> Option<u8> {
+        dispatch!(self, place => place.get_num_houses())
+    }
+
+    fn set_num_houses(&mut self, num: u8) {
+        dispatch!(self, place => place.set_num_houses(num))
+    }
+
+    fn get_price_of_house(&self) -> Option<u32> {
+        dispatch!(self, place => place.get_price_of_house())
+    }
+
+    fn get_price(&self) -> u32 {
+        dispatch!(self, place => place.get_price())
+    }
+
+    fn get_color(&self) -> BoardColor {
+        dispatch!(self, place => place.get_color())
+    }
+
+    fn is_mortgaged(&self) -> bool {
+        dispatch!(self, place => place.is_mortgaged())
+    }
+
+    fn set_mortgaged(&mut self, mortgaged: bool) -> u32 {
+        dispatch!(self, place => place.set_mortgaged(mortgaged))
+    }
+}
+
+pub fn get_place_list() -> Vec<Place> {
     vec![
-        Nothing::new_boxed(0, "Go"),
-        Estate::new_boxed(
+        Nothing::new_place(0, "Go"),
+        Estate::new_place(
             1,
             BoardColor::Brown,
             "Mediterranean Avenue",
             60,
             50,
-            vec![2, 4, 10, 30, 90, 160, 250],
+            [2, 4, 10, 30, 90, 160, 250],
         ),
-        CommunityChest::new_boxed(2),
-        Estate::new_boxed(
+        CommunityChest::new_place(2),
+        Estate::new_place(
             3,
             BoardColor::Brown,
             "Baltic Avenue",
             60,
             50,
-            vec![4, 8, 20, 60, 180, 320, 450],
+            [4, 8, 20, 60, 180, 320, 450],
         ),
-        IncomeTax::new_boxed(4),
-        Railroad::new_boxed(5, "Reading Railroad"),
-        Estate::new_boxed(
+        IncomeTax::new_place(4),
+        Railroad::new_place(5, "Reading Railroad"),
+        Estate::new_place(
             6,
             BoardColor::LightBlue,
             "Oriental Avenue",
             100,
             50,
-            vec![6, 12, 30, 90, 270, 400, 550],
+            [6, 12, 30, 90, 270, 400, 550],
         ),
-        Chance::new_boxed(7),
-        Estate::new_boxed(
+        Chance::new_place(7),
+        Estate::new_place(
             8,
             BoardColor::LightBlue,
             "Vermont Avenue",
             100,
             50,
-            vec![6, 12, 30, 90, 270, 400, 550],
+            [6, 12, 30, 90, 270, 400, 550],
         ),
-        Estate::new_boxed(
+        Estate::new_place(
             9,
             BoardColor::LightBlue,
             "Connecticut Avenue",
             120,
             50,
-            vec![8, 16, 40, 100, 300, 450, 600],
+            [8, 16, 40, 100, 300, 450, 600],
         ),
-        Nothing::new_boxed(10, "Just Visiting"),
-        Estate::new_boxed(
+        Nothing::new_place(10, "Just Visiting"),
+        Estate::new_place(
             11,
             BoardColor::LightPurple,
             "St. Charles Place",
             140,
             100,
-            vec![10, 20, 50, 150, 450, 625, 750],
+            [10, 20, 50, 150, 450, 625, 750],
         ),
-        Utilities::new_boxed(12, "Electric Company"),
-        Estate::new_boxed(
+        Utilities::new_place(12, "Electric Company"),
+        Estate::new_place(
             13,
             BoardColor::LightPurple,
             "States Avenue",
             140,
             100,
-            vec![10, 20, 50, 150, 450, 625, 750],
+            [10, 20, 50, 150, 450, 625, 750],
         ),
-        Estate::new_boxed(
+        Estate::new_place(
             14,
             BoardColor::LightPurple,
             "Virginia Avenue",
             160,
             100,
-            vec![12, 24, 60, 180, 500, 700, 900],
+            [12, 24, 60, 180, 500, 700, 900],
         ),
-        Railroad::new_boxed(15, "Pennsylvania Railroad"),
-        Estate::new_boxed(
+        Railroad::new_place(15, "Pennsylvania Railroad"),
+        Estate::new_place(
             16,
             BoardColor::Orange,
             "St. James Place",
             180,
             100,
-            vec![14, 28, 70, 200, 550, 750, 950],
+            [14, 28, 70, 200, 550, 750, 950],
         ),
-        CommunityChest::new_boxed(17),
-        Estate::new_boxed(
+        CommunityChest::new_place(17),
+        Estate::new_place(
             18,
             BoardColor::Orange,
             "Tennessee Avenue",
             180,
             100,
-            vec![14, 28, 70, 200, 550, 750, 950],
+            [14, 28, 70, 200, 550, 750, 950],
         ),
-        Estate::new_boxed(
+        Estate::new_place(
             19,
             BoardColor::Orange,
             "New York Avenue",
             200,
             100,
-            vec![16, 32, 80, 220, 600, 800, 1000],
+            [16, 32, 80, 220, 600, 800, 1000],
         ),
-        Nothing::new_boxed(20, "Free Parking"),
-        Estate::new_boxed(
+        Nothing::new_place(20, "Free Parking"),
+        Estate::new_place(
             21,
             BoardColor::Red,
             "Kentucky Avenue",
             220,
             150,
-            vec![18, 36, 90, 250, 700, 875, 1050],
+            [18, 36, 90, 250, 700, 875, 1050],
         ),
-        Chance::new_boxed(22),
-        Estate::new_boxed(
+        Chance::new_place(22),
+        Estate::new_place(
             23,
             BoardColor::Red,
             "Indiana Avenue",
             220,
             150,
-            vec![18, 36, 90, 250, 700, 875, 1050],
+            [18, 36, 90, 250, 700, 875, 1050],
         ),
-        Estate::new_boxed(
+        Estate::new_place(
             24,
             BoardColor::Red,
             "Illinois Avenue",
             240,
             150,
-            vec![20, 40, 100, 300, 750, 925, 1100],
+            [20, 40, 100, 300, 750, 925, 1100],
         ),
-        Railroad::new_boxed(25, "B. & O. Railroad"),
-        Estate::new_boxed(
+        Railroad::new_place(25, "B. & O. Railroad"),
+        Estate::new_place(
             26,
             BoardColor::Yellow,
             "Atlantic Avenue",
             260,
             150,
-            vec![22, 44, 110, 330, 800, 975, 1150],
+            [22, 44, 110, 330, 800, 975, 1150],
         ),
-        Estate::new_boxed(
+        Estate::new_place(
             27,
             BoardColor::Yellow,
             "Ventnor Avenue",
             260,
             150,
-            vec![22, 44, 110, 330, 800, 975, 1150],
+            [22, 44, 110, 330, 800, 975, 1150],
         ),
-        Utilities::new_boxed(28, "Water Works"),
-        Estate::new_boxed(
+        Utilities::new_place(28, "Water Works"),
+        Estate::new_place(
             29,
             BoardColor::Yellow,
             "Marvin Gardens",
             280,
             150,
-            vec![24, 48, 120, 360, 850, 1025, 1200],
+            [24, 48, 120, 360, 850, 1025, 1200],
         ),
-        GoToJail::new_boxed(30),
-        Estate::new_boxed(
+        GoToJail::new_place(30),
+        Estate::new_place(
             31,
             BoardColor::Green,
             "Pacific Avenue",
             300,
             200,
-            vec![26, 52, 130, 390, 900, 1100, 1275],
+            [26, 52, 130, 390, 900, 1100, 1275],
         ),
-        Estate::new_boxed(
+        Estate::new_place(
             32,
             BoardColor::Green,
             "North Carolina Avenue",
             300,
             200,
-            vec![26, 52, 130, 390, 900, 1100, 1275],
+            [26, 52, 130, 390, 900, 1100, 1275],
         ),
-        CommunityChest::new_boxed(33),
-        Estate::new_boxed(
+        CommunityChest::new_place(33),
+        Estate::new_place(
             34,
             BoardColor::Green,
             "Pennsylvania Avenue",
             320,
             200,
-            vec![28, 56, 150, 450, 1000, 1200, 1400],
+            [28, 56, 150, 450, 1000, 1200, 1400],
         ),
-        Railroad::new_boxed(35, "Short Line"),
-        Chance::new_boxed(36),
-        Estate::new_boxed(
+        Railroad::new_place(35, "Short Line"),
+        Chance::new_place(36),
+        Estate::new_place(
             37,
             BoardColor::Blue,
             "Park Place",
             350,
             200,
-            vec![35, 70, 175, 500, 1100, 1300, 1500],
+            [35, 70, 175, 500, 1100, 1300, 1500],
         ),
-        LuxuryTax::new_boxed(38),
-        Estate::new_boxed(
+        LuxuryTax::new_place(38),
+        Estate::new_place(
             39,
             BoardColor::Blue,
             "Boardwalk",
             400,
             200,
-            vec![50, 100, 200, 600, 1400, 1700, 2000],
+            [50, 100, 200, 600, 1400, 1700, 2000],
         ),
     ]
 }
@@ -1,6 +1,7 @@
 use crate::board::Board;
-use crate::places::{BoardColor, BoardPlace, EventKind};
+use crate::places::{BoardColor, BoardPlace, EventKind, Place};
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Nothing {
     id: usize,
     name: &'static str,
@@ -61,7 +62,7 @@ impl BoardPlace for Nothing {
 }
 
 impl Nothing {
-    pub fn new_boxed(id: usize, name: &'static str) -> Box<dyn BoardPlace + Send> {
-        Box::new(Nothing { id, name })
+    pub fn new_place(id: usize, name: &'static str) -> Place {
+        Place::Nothing(Nothing { id, name })
     }
 }
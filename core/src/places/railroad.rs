@@ -1,6 +1,7 @@
 use crate::board::Board;
-use crate::places::{BoardColor, BoardPlace, EventKind};
+use crate::places::{BoardColor, BoardPlace, EventKind, Place};
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Railroad {
     id: usize,
     name: &'static str,
@@ -85,8 +86,8 @@ impl BoardPlace for Railroad {
 }
 
 impl Railroad {
-    pub fn new_boxed(id: usize, name: &'static str) -> Box<dyn BoardPlace + Send> {
-        Box::new(Railroad {
+    pub fn new_place(id: usize, name: &'static str) -> Place {
+        Place::Railroad(Railroad {
             id,
             name,
             owner: None,
@@ -95,14 +96,7 @@ impl Railroad {
     }
 
     fn get_own_num(&self, board: &Board) -> u32 {
-        board
-            .places
-            .iter()
-            .filter(|place| {
-                place.get_color() == BoardColor::Railroad
-                    && place.get_owner() == self.owner
-                    && !place.is_mortgaged()
-            })
-            .count() as u32
+        self.owner
+            .map_or(0, |owner| board.get_own_num(BoardColor::Railroad, owner))
     }
 }
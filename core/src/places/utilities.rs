@@ -1,7 +1,7 @@
 use crate::board::Board;
-use crate::dice_rolling::DiceRolling;
-use crate::places::{BoardColor, BoardPlace, EventKind};
+use crate::places::{BoardColor, BoardPlace, EventKind, Place};
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Utilities {
     id: usize,
     name: &'static str,
@@ -33,12 +33,12 @@ impl BoardPlace for Utilities {
             } else if self.mortgaged {
                 EventKind::None("The place is mortgaged.")
             } else {
-                let rent = match self.get_own_num(board) {
-                    1 => DiceRolling::roll().unwrap() * 4,
-                    2 => DiceRolling::roll().unwrap() * 10,
+                let multiplier = match self.get_own_num(board) {
+                    1 => 4,
+                    2 => 10,
                     _ => panic!("The number of utilities is invalid."),
                 };
-                EventKind::PayToOther(self.get_place_name(), owner, rent)
+                EventKind::PayToOtherByDice(self.get_place_name(), owner, multiplier)
             }
         } else {
             EventKind::GivePlace(self.id, 150)
@@ -84,8 +84,8 @@ impl BoardPlace for Utilities {
 }
 
 impl Utilities {
-    pub fn new_boxed(id: usize, name: &'static str) -> Box<dyn BoardPlace + Send> {
-        Box::new(Utilities {
+    pub fn new_place(id: usize, name: &'static str) -> Place {
+        Place::Utilities(Utilities {
             id,
             name,
             owner: None,
@@ -94,14 +94,7 @@ impl Utilities {
     }
 
     fn get_own_num(&self, board: &Board) -> u32 {
-        board
-            .places
-            .iter()
-            .filter(|place| {
-                place.get_color() == BoardColor::Utilities
-                    && place.get_owner() == self.owner
-                    && !place.is_mortgaged()
-            })
-            .count() as u32
+        self.owner
+            .map_or(0, |owner| board.get_own_num(BoardColor::Utilities, owner))
     }
 }
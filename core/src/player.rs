@@ -1,10 +1,9 @@
-use crate::board::Board;
 use crate::strategy::PlayerStrategy;
 
 ///
 /// Represents the state of a player.
 ///
-#[derive(PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub enum PlayerState {
     None,
     Bankrupted,
@@ -14,6 +13,7 @@ pub enum PlayerState {
 ///
 /// Holds the information of a player.
 ///
+#[derive(Clone)]
 pub struct Player {
     pub player_id: usize,
     pub money: u32,
@@ -37,50 +37,43 @@ impl Player {
     }
 
     ///
-    /// Makes the player pay the rent.
+    /// Gets the strategy of the player.
     ///
-    pub fn pay(&mut self, board: &mut Board, dollars: u32) -> (Result<(), u32>, Vec<String>) {
-        match self.strategy.raise(
-            dollars,
-            board,
-            self.player_id,
-            &mut self.money,
-            &self.state,
-            self.position,
-        ) {
-            Ok(_) => (
-                Ok(()),
-                vec![format!(
-                    "[PLAYER{}] Money: ${} -> ${}",
-                    self.player_id,
-                    self.money + dollars,
-                    self.money
-                )],
-            ),
-            Err(money) => {
-                assert!(money < dollars);
+    pub fn get_strategy(&self) -> &(dyn PlayerStrategy + Send) {
+        self.strategy.as_ref()
+    }
 
-                self.money = 0;
-                self.state = PlayerState::Bankrupted;
+    ///
+    /// Replaces the strategy of the player.
+    ///
+    pub fn set_strategy(&mut self, strategy: Box<dyn PlayerStrategy + Send>) {
+        self.strategy = strategy;
+    }
 
-                (
-                    Err(money),
-                    vec![format!("[PLAYER{}] Bankrupted", self.player_id)],
-                )
-            }
-        }
+    ///
+    /// Gets whether the player is bankrupted.
+    ///
+    pub fn is_bankrupted(&self) -> bool {
+        self.state == PlayerState::Bankrupted
     }
 
     ///
-    /// Makes the player do investment within the budget.
+    /// Makes the player pay money in hand.
+    ///
+    /// If they cannot afford it, they get bankrupted and the money they have is returned as an error.
     ///
-    pub fn invest(&mut self, board: &mut Board) {
-        self.strategy.invest(
-            board,
-            self.player_id,
-            &mut self.money,
-            &self.state,
-            self.position,
-        );
+    pub fn pay(&mut self, dollars: u32) -> Result<(), u32> {
+        if self.money >= dollars {
+            self.money -= dollars;
+
+            Ok(())
+        } else {
+            let money = self.money;
+
+            self.money = 0;
+            self.state = PlayerState::Bankrupted;
+
+            Err(money)
+        }
     }
 }
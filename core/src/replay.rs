@@ -0,0 +1,272 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::board::GameSession;
+use crate::observer::{StrategyAction, TradeOffer};
+use crate::serialization::{GameInfo, StrategyInfo};
+use crate::strategy::{GameView, PlayerStrategy};
+
+///
+/// Holds what happened in a turn.
+///
+/// `calls` has the player asked and the number of the decisions they took for each call of the strategies, in order.
+///
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayTurn {
+    pub player_id: usize,
+    pub rolls: Vec<(u32, u32)>,
+    pub decisions: Vec<(usize, StrategyAction)>,
+    #[serde(default)]
+    pub calls: Vec<(usize, usize)>,
+}
+
+///
+/// Holds everything needed to reproduce a game.
+///
+/// The dice rolls and the decisions are replayed as they were.
+/// After they run out, the seed drives the dices and the strategies of `initial` decide.
+///
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Replay {
+    pub initial: GameInfo,
+    pub seed: u64,
+    pub turns: Vec<ReplayTurn>,
+    pub last: Option<GameInfo>,
+}
+
+impl Replay {
+    ///
+    /// Generates an empty replay starting from the game.
+    ///
+    pub fn new(initial: GameInfo, seed: u64) -> Self {
+        Replay {
+            initial,
+            seed,
+            turns: Vec::new(),
+            last: None,
+        }
+    }
+
+    ///
+    /// Reconstructs a replay from JSON.
+    ///
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|err| err.to_string())
+    }
+
+    ///
+    /// Parses the replay into a text in JSON.
+    ///
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    ///
+    /// Reproduces the game at the designated turn, counted from the beginning of the replay.
+    ///
+    pub fn goto(&self, turn: usize) -> Result<GameSession, String> {
+        let mut game = GameSession::from_replay(self)?;
+        for _ in 0..turn {
+            game.spend_one_turn();
+        }
+        Ok(game)
+    }
+
+    ///
+    /// Checks that the replay reproduces the same dice rolls, decisions and final state.
+    ///
+    pub fn verify(&self) -> Result<(), String> {
+        let mut game = GameSession::from_replay(self)?;
+        game.recording = Some(Replay::new(self.initial.clone(), self.seed));
+
+        for (turn, expected) in self.turns.iter().enumerate() {
+            game.spend_one_turn();
+
+            let actual = game.recording.as_ref().unwrap().turns.last().unwrap();
+            if actual.player_id != expected.player_id {
+                return Err(format!("Turn {}: the player in turn differs.", turn));
+            }
+            if actual.rolls != expected.rolls {
+                return Err(format!("Turn {}: the dice rolls differ.", turn));
+            }
+            if actual.decisions != expected.decisions {
+                return Err(format!("Turn {}: the decisions differ.", turn));
+            }
+        }
+
+        if let Some(last) = &self.last {
+            let expected = serde_json::to_string(last).unwrap();
+            let actual = serde_json::to_string(&game.to_info()).unwrap();
+            if expected != actual {
+                return Err("The final state differs.".to_string());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl GameSession {
+    ///
+    /// Starts recording the game from the current state.
+    ///
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Replay::new(self.to_info(), self.get_seed()));
+        self.set_seed(self.get_seed());
+    }
+
+    ///
+    /// Gets the replay recorded so far, if recording.
+    ///
+    pub fn get_replay(&self) -> Option<Replay> {
+        let mut replay = self.recording.clone()?;
+        replay.last = Some(self.to_info());
+        Some(replay)
+    }
+
+    ///
+    /// Reconstructs a game at the beginning of the replay.
+    ///
+    /// The strategies are replaced with scripted ones taking the recorded decisions.
+    ///
+    pub fn from_replay(replay: &Replay) -> Result<Self, String> {
+        let mut game = GameSession::from_info(&replay.initial)?;
+        game.set_seed(replay.seed);
+        game.script_dices(replay.turns.iter().flat_map(|turn| turn.rolls.clone()));
+
+        let player_num = game.players.len();
+        let mut calls = vec![VecDeque::new(); player_num];
+        let mut trades = vec![VecDeque::new(); player_num];
+        for turn in &replay.turns {
+            let mut decisions = turn.decisions.iter();
+            for &(player_id, num) in &turn.calls {
+                if player_id >= player_num {
+                    return Err(format!("PLAYER{} does not exist.", player_id));
+                }
+                let actions = decisions
+                    .by_ref()
+                    .take(num)
+                    .map(|(_, action)| action.clone())
+                    .collect::<Vec<_>>();
+                calls[player_id].push_back(actions);
+            }
+
+            // The trades recorded are the ones the partners accepted.
+            for (player_id, action) in &turn.decisions {
+                if let StrategyAction::Trade(offer) = action {
+                    if offer.partner >= player_num {
+                        return Err(format!("PLAYER{} does not exist.", offer.partner));
+                    }
+                    trades[offer.partner].push_back((*player_id, offer.clone()));
+                }
+            }
+        }
+
+        for (player_id, (calls, trades)) in calls.into_iter().zip(trades).enumerate() {
+            let fallback = game.get_player(player_id).get_strategy().clone_boxed();
+            game.get_player_mut(player_id)
+                .set_strategy(Box::new(ScriptedStrategy {
+                    calls: RefCell::new(calls),
+                    trades: RefCell::new(trades),
+                    fallback,
+                }));
+        }
+        Ok(game)
+    }
+
+    ///
+    /// Gets the number of the decisions recorded in the current turn.
+    ///
+    pub(crate) fn get_recorded_decision_num(&self) -> usize {
+        self.recording
+            .as_ref()
+            .and_then(|recording| recording.turns.last())
+            .map_or(0, |turn| turn.decisions.len())
+    }
+
+    ///
+    /// Records that the strategy of the player was asked, and took the decisions recorded since `start`.
+    ///
+    pub(crate) fn record_call(&mut self, player_id: usize, start: usize) {
+        if let Some(turn) = self
+            .recording
+            .as_mut()
+            .and_then(|recording| recording.turns.last_mut())
+        {
+            turn.calls.push((player_id, turn.decisions.len() - start));
+        }
+    }
+}
+
+///
+/// Takes the decisions recorded in a replay for each call, and follows the fallback after they run out.
+///
+/// The trades recorded are accepted, since the player accepted them originally.
+///
+#[derive(Clone)]
+struct ScriptedStrategy {
+    calls: RefCell<VecDeque<Vec<StrategyAction>>>,
+    trades: RefCell<VecDeque<(usize, TradeOffer)>>,
+    fallback: Box<dyn PlayerStrategy + Send>,
+}
+
+impl ScriptedStrategy {
+    ///
+    /// Takes the decisions of the next call, or asks the fallback if there are no more.
+    ///
+    fn next_call<F>(&self, decide: F) -> Vec<StrategyAction>
+    where
+        F: FnOnce(&dyn PlayerStrategy) -> Vec<StrategyAction>,
+    {
+        let actions = self.calls.borrow_mut().pop_front();
+        actions.unwrap_or_else(|| decide(self.fallback.as_ref()))
+    }
+}
+
+impl PlayerStrategy for ScriptedStrategy {
+    fn raise(&self, view: &GameView, debt: u32) -> Vec<StrategyAction> {
+        self.next_call(|fallback| fallback.raise(view, debt))
+    }
+
+    fn invest(&self, view: &GameView) -> Vec<StrategyAction> {
+        self.next_call(|fallback| fallback.invest(view))
+    }
+
+    fn buy(&self, view: &GameView, place_id: usize, price: u32) -> Vec<StrategyAction> {
+        self.next_call(|fallback| fallback.buy(view, place_id, price))
+    }
+
+    fn leave_jail(&self, view: &GameView) -> Vec<StrategyAction> {
+        self.next_call(|fallback| fallback.leave_jail(view))
+    }
+
+    fn accepts_trade(&self, view: &GameView, proposer: usize, offer: &TradeOffer) -> bool {
+        let mut trades = self.trades.borrow_mut();
+        if trades
+            .front()
+            .is_some_and(|(id, recorded)| *id == proposer && recorded == offer)
+        {
+            trades.pop_front();
+            return true;
+        }
+        self.fallback.accepts_trade(view, proposer, offer)
+    }
+
+    fn get_info(&self) -> StrategyInfo {
+        self.fallback.get_info()
+    }
+
+    fn take_reports(&self) -> Vec<String> {
+        self.fallback.take_reports()
+    }
+
+    fn set_seed(&self, seed: u64) {
+        self.fallback.set_seed(seed);
+    }
+
+    fn clone_boxed(&self) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(self.clone())
+    }
+}
@@ -1,15 +1,16 @@
 use calamine::{open_workbook, DataType, Range, RangeDeserializerBuilder, Reader, Xlsx};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::board::{Board, GameSession};
-use crate::places::{BoardColor, BoardPlace};
+use crate::places::{BoardColor, BoardPlace, Place};
 use crate::player::{Player, PlayerState};
-use crate::strategy::{ExpensiveHousesProtectionStrategy, PlayerStrategy};
+use crate::strategy::{PlayerStrategy, StrategyRegistry};
 
 ///
 /// Holds information of a game in a serializable format.
 ///
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GameInfo {
     pub turn: usize,
     pub players: Vec<PlayerInfo>,
@@ -19,19 +20,67 @@ pub struct GameInfo {
 ///
 /// Holds information of a player in a serializable format.
 ///
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct PlayerInfo {
     pub player_id: usize,
     pub money: u32,
     pub is_bankrupted: bool,
     pub jail_turn: Option<u8>,
     pub position: usize,
+    #[serde(default)]
+    pub strategy: Option<StrategyInfo>,
+}
+
+///
+/// Holds the name and the parameters of a strategy in a serializable format.
+///
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct StrategyInfo {
+    pub name: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+impl StrategyInfo {
+    ///
+    /// Generates information of a strategy without parameters.
+    ///
+    pub fn new(name: &str) -> Self {
+        StrategyInfo {
+            name: name.to_string(),
+            params: Value::Null,
+        }
+    }
+
+    ///
+    /// Generates information of a strategy with parameters.
+    ///
+    pub fn with_params<T: Serialize>(name: &str, params: &T) -> Self {
+        StrategyInfo {
+            name: name.to_string(),
+            params: serde_json::to_value(params).unwrap(),
+        }
+    }
+
+    ///
+    /// Reconstructs information of a strategy from JSON.
+    ///
+    pub fn from_json(json: &str) -> Self {
+        serde_json::from_str(json).unwrap()
+    }
+
+    ///
+    /// Parses information of the strategy into a text in JSON.
+    ///
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
 }
 
 ///
 /// Holds information of a place in a serializable format.
 ///
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct PlaceInfo {
     pub place_id: usize,
     pub owner: Option<usize>,
@@ -63,11 +112,12 @@ impl From<(i64, i64, String, i64, i64)> for PlayerInfo {
                 None
             },
             position: position as usize,
+            strategy: None,
         }
     }
 }
 
-impl dyn BoardPlace + Send {
+impl Place {
     pub fn to_place_prop(&self, board: &Board) -> PlaceProp {
         let place_id = self.get_id();
         let name = self.get_place_name().to_string();
@@ -95,36 +145,51 @@ impl GameSession {
     ///
     /// Reconstructs a game session from GameInfo.
     ///
-    pub fn from_info(game_info: &GameInfo) -> Self {
+    /// Strategies of the players are looked up in the registry installed by `StrategyRegistry::install`.
+    /// Fails if any of them cannot be constructed.
+    ///
+    pub fn from_info(game_info: &GameInfo) -> Result<Self, String> {
+        GameSession::from_info_with(game_info, StrategyRegistry::get_installed())
+    }
+
+    ///
+    /// Reconstructs a game session from GameInfo, looking up the strategies in the registry.
+    ///
+    /// Fails if any of the strategies cannot be constructed.
+    ///
+    pub fn from_info_with(
+        game_info: &GameInfo,
+        registry: &StrategyRegistry,
+    ) -> Result<Self, String> {
         let mut game = GameSession::new(game_info.players.len() as u32);
 
         let mut players = Vec::new();
         for player_info in &game_info.players {
-            players.push(Player::from_info(
-                player_info,
-                ExpensiveHousesProtectionStrategy::new_boxed(),
-            ));
+            let strategy = registry
+                .create_or_default(player_info.strategy.as_ref())
+                .map_err(|msg| format!("PLAYER{}: {}", player_info.player_id, msg))?;
+            players.push(Player::from_info(player_info, strategy));
         }
         game.players = players;
 
         game.turn = game_info.turn;
         game.board = Board::from_infos(&game_info.places);
 
-        game
+        Ok(game)
     }
 
     ///
     /// Reconstructs a game session from JSON.
     ///
-    pub fn from_json(json: &str) -> Self {
-        let game_info: GameInfo = serde_json::from_str(json).unwrap();
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let game_info: GameInfo = serde_json::from_str(json).map_err(|err| err.to_string())?;
         GameSession::from_info(&game_info)
     }
 
     ///
-    /// Parses information of the game session into a text in JSON.
+    /// Turns the game session into `GameInfo`, which is serializable.
     ///
-    pub fn to_json(&self) -> String {
+    pub fn to_info(&self) -> GameInfo {
         let turn = self.turn;
         let players = self
             .players
@@ -133,39 +198,66 @@ impl GameSession {
             .collect::<Vec<_>>();
         let places = self.board.get_infos();
 
-        let game_info = GameInfo {
+        GameInfo {
             turn,
             players,
             places,
-        };
+        }
+    }
 
-        serde_json::to_string_pretty(&game_info).unwrap()
+    ///
+    /// Parses information of the game session into a text in JSON.
+    ///
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.to_info()).unwrap()
     }
 
     ///
     /// Imports information of a game from a .xlsx file.
     ///
-    pub fn from_excel(file_name: &str) -> Self {
+    /// Fails if any of the strategies cannot be constructed.
+    ///
+    pub fn from_excel(file_name: &str) -> Result<Self, String> {
         let mut workbook: Xlsx<_> = open_workbook(file_name).unwrap();
 
         let turn = workbook.worksheet_range("Turn").unwrap().unwrap();
         let turn = turn.get_value((0, 1)).unwrap().as_i64().unwrap() as usize;
 
+        // The sheet may have two optional columns, the name and the parameters in JSON of the strategy.
         let players_sheet = workbook.worksheet_range("Players").unwrap().unwrap();
-        let players_sheet = RangeDeserializerBuilder::new()
-            .from_range(&players_sheet)
-            .unwrap();
         let players: Vec<Player> = players_sheet
-            .into_iter()
-            .map(|player| {
-                let player_info: (i64, i64, String, i64, i64) = player.unwrap();
-
-                Player::from_info(
-                    &(player_info.into()),
-                    ExpensiveHousesProtectionStrategy::new_boxed(),
-                )
+            .rows()
+            .skip(1)
+            .map(|row| {
+                let player_info: (i64, i64, String, i64, i64) = (
+                    row[0].as_i64().unwrap(),
+                    row[1].as_i64().unwrap(),
+                    row[2].get_string().unwrap().to_string(),
+                    row[3].as_i64().unwrap(),
+                    row[4].as_i64().unwrap(),
+                );
+                let mut player_info: PlayerInfo = player_info.into();
+
+                let params = row
+                    .get(6)
+                    .and_then(|params| params.get_string())
+                    .map_or(Ok(Value::Null), serde_json::from_str)
+                    .map_err(|err| format!("PLAYER{}: {}", player_info.player_id, err))?;
+                player_info.strategy = row
+                    .get(5)
+                    .and_then(|name| name.get_string())
+                    .filter(|name| !name.is_empty())
+                    .map(|name| StrategyInfo {
+                        name: name.to_string(),
+                        params,
+                    });
+
+                let strategy = StrategyRegistry::get_installed()
+                    .create_or_default(player_info.strategy.as_ref())
+                    .map_err(|msg| format!("PLAYER{}: {}", player_info.player_id, msg))?;
+                Ok(Player::from_info(&player_info, strategy))
             })
-            .collect();
+            .collect::<Result<_, String>>()?;
 
         let places_sheet = workbook.worksheet_range("Places").unwrap().unwrap();
         let board = Board::from_rows(places_sheet);
@@ -175,7 +267,7 @@ impl GameSession {
         game.turn = turn;
         game.board = board;
 
-        game
+        Ok(game)
     }
 }
 
@@ -185,23 +277,32 @@ impl Player {
     ///
     pub fn from_info(info: &PlayerInfo, strategy: Box<dyn PlayerStrategy + Send>) -> Self {
         let mut player = Player::new(info.player_id, strategy);
-        player.money = info.money;
+        player.set_info(info);
+
+        player
+    }
+
+    ///
+    /// Overwrites the player data with `PlayerInfo`, keeping the strategy.
+    ///
+    pub fn set_info(&mut self, info: &PlayerInfo) {
+        assert_eq!(self.player_id, info.player_id);
+
+        self.money = info.money;
 
         if info.is_bankrupted {
-            player.state = PlayerState::Bankrupted;
+            self.state = PlayerState::Bankrupted;
 
             assert_eq!(info.jail_turn, None);
         } else {
-            player.state = if let Some(jail_turn) = info.jail_turn {
+            self.state = if let Some(jail_turn) = info.jail_turn {
                 PlayerState::InJail(jail_turn)
             } else {
                 PlayerState::None
             };
         }
 
-        player.position = info.position;
-
-        player
+        self.position = info.position;
     }
 
     ///
@@ -220,6 +321,7 @@ impl Player {
             is_bankrupted,
             jail_turn,
             position: self.position,
+            strategy: Some(self.get_strategy().get_info()),
         }
     }
 }
@@ -242,6 +344,7 @@ impl Board {
             }
             place.set_mortgaged(info.is_mortgaged);
         }
+        board.reindex();
 
         board
     }
@@ -0,0 +1,315 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+use std::thread::{available_parallelism, scope};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::board::{GameSession, LogLevel};
+use crate::ledger::Ledger;
+use crate::observer::GameObserver;
+use crate::player::PlayerState;
+use crate::serialization::GameInfo;
+use crate::statistics::GameStatistics;
+
+///
+/// Creates an observer attached to each game of a simulation.
+///
+/// To collect results across the games, make the observers share a handle such as `Arc<Mutex<T>>`.
+///
+pub type ObserverFactory = Box<dyn Fn() -> Box<dyn GameObserver + Send> + Send + Sync>;
+
+///
+/// Gets notified with the number of the games completed and the number of the games in total.
+///
+/// It is called from the threads running the games.
+///
+pub type ProgressCallback = Box<dyn Fn(u32, u32) + Send + Sync>;
+
+///
+/// Holds a result of survival analysis.
+///
+#[derive(Serialize, Deserialize)]
+pub struct SurvivalResult {
+    pub survival_rates: Vec<f32>,
+    pub statistics: GameStatistics,
+}
+
+///
+/// A handle to cancel a simulation and watch its progress from another thread.
+///
+#[derive(Clone, Default)]
+pub struct SimulationHandle {
+    cancelled: Arc<AtomicBool>,
+    completed: Arc<AtomicU32>,
+}
+
+impl SimulationHandle {
+    ///
+    /// Cancels the simulation. The games in progress are abandoned.
+    ///
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    ///
+    /// Gets whether the simulation is cancelled.
+    ///
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    ///
+    /// Gets the number of the games completed.
+    ///
+    pub fn get_completed(&self) -> u32 {
+        self.completed.load(Ordering::Relaxed)
+    }
+}
+
+///
+/// Runs Monte Carlo simulations from a game.
+///
+/// Every game is cloned from the starting one, so the strategies keep their state in each of them.
+/// The games are spread across `threads` threads. Each game rolls the dices with its own seed derived from `seed`,
+/// and reseeds the strategies from it as well, so the results are the same regardless of the number of the threads.
+///
+pub struct Simulation {
+    game: GameSession,
+    pub iteration: u32,
+    pub depth: usize,
+    pub seed: Option<u64>,
+    pub threads: usize,
+    factories: Vec<ObserverFactory>,
+    progress: Option<ProgressCallback>,
+    handle: SimulationHandle,
+}
+
+impl Simulation {
+    ///
+    /// Generates a simulation which plays `depth` turns `iteration` times.
+    ///
+    /// Fails if any of the strategies cannot be constructed.
+    ///
+    pub fn new(game: GameInfo, iteration: u32, depth: usize) -> Result<Self, String> {
+        let session = GameSession::from_info(&game)?;
+        Ok(Simulation::from_session(&session, iteration, depth))
+    }
+
+    ///
+    /// Generates a simulation from the session without serializing it.
+    ///
+    /// The logs, the records and the history of the session are not carried over to the games,
+    /// and the games do not log anything.
+    ///
+    pub fn from_session(session: &GameSession, iteration: u32, depth: usize) -> Self {
+        let mut game = session.clone();
+        game.logs.clear();
+        game.set_log_level(LogLevel::Off);
+        game.ledger = Ledger::default();
+        game.statistics = GameStatistics::new(game.players.len(), game.board.places.len());
+        game.recording = None;
+        game.set_history_capacity(0);
+        game.set_auditing(false);
+
+        Simulation {
+            game,
+            iteration,
+            depth,
+            seed: None,
+            threads: available_parallelism().map_or(1, |threads| threads.get()),
+            factories: Vec::new(),
+            progress: None,
+            handle: SimulationHandle::default(),
+        }
+    }
+
+    ///
+    /// Attaches observers created by the factory to every game.
+    ///
+    pub fn attach(&mut self, factory: ObserverFactory) {
+        self.factories.push(factory);
+    }
+
+    ///
+    /// Reports the progress to the callback every time a game is completed.
+    ///
+    pub fn set_progress(&mut self, progress: ProgressCallback) {
+        self.progress = Some(progress);
+    }
+
+    ///
+    /// Gets a handle to cancel the simulation and watch its progress.
+    ///
+    pub fn get_handle(&self) -> SimulationHandle {
+        self.handle.clone()
+    }
+
+    ///
+    /// Runs the simulation, and returns the results of the games completed in order.
+    ///
+    /// Each game has its own result starting from `R::default()`.
+    /// `on_turn` is called after every turn with the index of the turn, and `on_finish` is called after every game.
+    /// A game stops early if one of the observers requests.
+    /// If the simulation is cancelled, the games which have not been completed are left out.
+    ///
+    pub fn run<R, T, F>(&self, on_turn: T, on_finish: F) -> Vec<R>
+    where
+        R: Default + Send,
+        T: Fn(&mut R, usize, &GameSession) + Sync,
+        F: Fn(&mut R, &GameSession) + Sync,
+    {
+        let mut rng = StdRng::seed_from_u64(self.seed.unwrap_or_else(rand::random));
+        let seeds = (0..self.iteration)
+            .map(|_| rng.gen::<u64>())
+            .collect::<Vec<_>>();
+        let next = AtomicU32::new(0);
+        let context = Context {
+            factories: &self.factories,
+            progress: self.progress.as_ref(),
+            handle: &self.handle,
+            iteration: self.iteration,
+            depth: self.depth,
+        };
+
+        let mut results = scope(|s| {
+            let workers = (0..self.threads.max(1))
+                .map(|_| {
+                    let base = self.game.clone();
+                    let (seeds, next, on_turn, on_finish) = (&seeds, &next, &on_turn, &on_finish);
+                    s.spawn(move || {
+                        let mut results = Vec::new();
+                        loop {
+                            let idx = next.fetch_add(1, Ordering::Relaxed);
+                            if idx >= context.iteration || context.handle.is_cancelled() {
+                                break;
+                            }
+
+                            let mut game = base.clone();
+                            let seed = seeds[idx as usize];
+                            game.set_seed(seed);
+                            for player in &game.players {
+                                player
+                                    .get_strategy()
+                                    .set_seed(get_strategy_seed(seed, player.player_id));
+                            }
+                            if let Some(result) = context.play(game, on_turn, on_finish) {
+                                results.push((idx, result));
+                            }
+                        }
+                        results
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        // Merges the results in the order of the games, not in the order of completion.
+        results.sort_by_key(|(idx, _)| *idx);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    ///
+    /// Calculates the rates of the players surviving through the simulation.
+    ///
+    /// The statistics of the games are aggregated as well.
+    /// If the simulation is cancelled, the rates are calculated from the games completed.
+    ///
+    pub fn get_survival(&self) -> SurvivalResult {
+        let results = self.run(
+            |_, _, _| {},
+            |result: &mut (Vec<bool>, GameStatistics), game| {
+                result.0 = game
+                    .players
+                    .iter()
+                    .map(|player| player.state != PlayerState::Bankrupted)
+                    .collect();
+                result.1 = game.statistics.clone();
+            },
+        );
+
+        let mut counter = vec![0_u32; self.game.players.len()];
+        let mut statistics = GameStatistics::default();
+        for (survivals, game_statistics) in &results {
+            for (cnt, &survived) in counter.iter_mut().zip(survivals) {
+                if survived {
+                    *cnt += 1;
+                }
+            }
+            statistics.merge(game_statistics);
+        }
+
+        let games = results.len().max(1);
+        let survival_rates = counter
+            .iter()
+            .map(|&count| count as f32 / games as f32)
+            .collect();
+
+        SurvivalResult {
+            survival_rates,
+            statistics,
+        }
+    }
+}
+
+///
+/// What the threads running the games share.
+///
+#[derive(Clone, Copy)]
+struct Context<'a> {
+    factories: &'a [ObserverFactory],
+    progress: Option<&'a ProgressCallback>,
+    handle: &'a SimulationHandle,
+    iteration: u32,
+    depth: usize,
+}
+
+impl Context<'_> {
+    ///
+    /// Plays a game, and returns its result unless the simulation is cancelled.
+    ///
+    fn play<R, T, F>(&self, mut game: GameSession, on_turn: &T, on_finish: &F) -> Option<R>
+    where
+        R: Default,
+        T: Fn(&mut R, usize, &GameSession),
+        F: Fn(&mut R, &GameSession),
+    {
+        for factory in self.factories {
+            game.add_observer(factory());
+        }
+
+        let mut result = R::default();
+        for turn in 0..self.depth {
+            if self.handle.is_cancelled() {
+                return None;
+            }
+
+            game.spend_one_turn();
+            on_turn(&mut result, turn, &game);
+
+            if game.should_stop() {
+                break;
+            }
+        }
+        on_finish(&mut result, &game);
+
+        let completed = self.handle.completed.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Some(progress) = self.progress {
+            progress(completed, self.iteration);
+        }
+
+        Some(result)
+    }
+}
+
+///
+/// Derives the seed of the strategy of the player from the seed of a game.
+///
+pub(crate) fn get_strategy_seed(seed: u64, player_id: usize) -> u64 {
+    StdRng::seed_from_u64(seed ^ (player_id as u64 + 1)).gen()
+}
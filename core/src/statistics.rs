@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+
+///
+/// Holds running statistics of games.
+///
+/// Vectors indexed by players have an element for each player, and ones indexed by places have an element for each place.
+///
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct GameStatistics {
+    pub games: u32,
+    pub landings: Vec<u32>,
+    pub rent_by_place: Vec<u32>,
+    pub rent_paid: Vec<u32>,
+    pub rent_received: Vec<u32>,
+    pub go_passes: Vec<u32>,
+    pub jail_entries: Vec<u32>,
+    pub jail_exits: Vec<u32>,
+    pub houses_built: Vec<u32>,
+    pub houses_sold: Vec<u32>,
+    pub mortgages: Vec<u32>,
+    pub turns_survived: Vec<u32>,
+}
+
+///
+/// Adds the counts element-wise, extending the destination if it is shorter.
+///
+fn add_counts(dst: &mut Vec<u32>, src: &[u32]) {
+    if dst.len() < src.len() {
+        dst.resize(src.len(), 0);
+    }
+    for (dst, src) in dst.iter_mut().zip(src) {
+        *dst += src;
+    }
+}
+
+impl GameStatistics {
+    ///
+    /// Generates empty statistics of a game.
+    ///
+    pub fn new(player_num: usize, place_num: usize) -> Self {
+        GameStatistics {
+            games: 1,
+            landings: vec![0; place_num],
+            rent_by_place: vec![0; place_num],
+            rent_paid: vec![0; player_num],
+            rent_received: vec![0; player_num],
+            go_passes: vec![0; player_num],
+            jail_entries: vec![0; player_num],
+            jail_exits: vec![0; player_num],
+            houses_built: vec![0; player_num],
+            houses_sold: vec![0; player_num],
+            mortgages: vec![0; player_num],
+            turns_survived: vec![0; player_num],
+        }
+    }
+
+    ///
+    /// Aggregates statistics of another game (or other games).
+    ///
+    pub fn merge(&mut self, other: &GameStatistics) {
+        self.games += other.games;
+        add_counts(&mut self.landings, &other.landings);
+        add_counts(&mut self.rent_by_place, &other.rent_by_place);
+        add_counts(&mut self.rent_paid, &other.rent_paid);
+        add_counts(&mut self.rent_received, &other.rent_received);
+        add_counts(&mut self.go_passes, &other.go_passes);
+        add_counts(&mut self.jail_entries, &other.jail_entries);
+        add_counts(&mut self.jail_exits, &other.jail_exits);
+        add_counts(&mut self.houses_built, &other.houses_built);
+        add_counts(&mut self.houses_sold, &other.houses_sold);
+        add_counts(&mut self.mortgages, &other.mortgages);
+        add_counts(&mut self.turns_survived, &other.turns_survived);
+    }
+
+    ///
+    /// Parses the statistics into a text in JSON.
+    ///
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+///
+/// Gets the 95% Wilson score interval of the rate observed in the trials.
+///
+pub fn get_wilson_interval(rate: f64, trials: u32) -> (f64, f64) {
+    if trials == 0 {
+        return (0.0, 1.0);
+    }
+
+    let z = 1.96_f64;
+    let n = trials as f64;
+    let denominator = 1.0 + z * z / n;
+    let center = (rate + z * z / (2.0 * n)) / denominator;
+    let half = z * (rate * (1.0 - rate) / n + z * z / (4.0 * n * n)).sqrt() / denominator;
+    ((center - half).max(0.0), (center + half).min(1.0))
+}
@@ -0,0 +1,69 @@
+use crate::observer::StrategyAction;
+use crate::places::BoardPlace;
+use crate::serialization::StrategyInfo;
+use crate::strategy::{
+    build_within, get_monopolized_colors, unmortgage_within, ExpensiveHousesProtectionStrategy,
+    GameView, Plan, PlayerStrategy,
+};
+
+///
+/// Spends all of the money on houses, keeping no reserve.
+///
+#[derive(Clone)]
+pub struct AggressiveBuilderStrategy;
+
+impl AggressiveBuilderStrategy {
+    ///
+    /// The name of the strategy in `StrategyRegistry`.
+    ///
+    pub const NAME: &'static str = "aggressive_builder";
+
+    pub fn new_boxed() -> Box<dyn PlayerStrategy + Send> {
+        Box::new(AggressiveBuilderStrategy {})
+    }
+
+    ///
+    /// Unmortgages places and builds houses with all of the money.
+    ///
+    fn invest_on(&self, plan: &mut Plan) {
+        let board = plan.board;
+        let player_id = plan.player_id;
+        let mut usable = plan.get_money();
+
+        let colors = get_monopolized_colors(board, player_id);
+        for color in &colors {
+            if !unmortgage_within(plan, &mut usable, |place| place.get_color() == *color) {
+                return;
+            }
+        }
+
+        // Builds on the expensive colors first, since they yield more rent.
+        for color in colors.iter().rev() {
+            if !build_within(plan, color, 5, &mut usable) {
+                return;
+            }
+        }
+
+        unmortgage_within(plan, &mut usable, |_| true);
+    }
+}
+
+impl PlayerStrategy for AggressiveBuilderStrategy {
+    fn raise(&self, view: &GameView, debt: u32) -> Vec<StrategyAction> {
+        ExpensiveHousesProtectionStrategy::default().raise(view, debt)
+    }
+
+    fn invest(&self, view: &GameView) -> Vec<StrategyAction> {
+        let mut plan = Plan::new(view);
+        self.invest_on(&mut plan);
+        plan.into_actions()
+    }
+
+    fn get_info(&self) -> StrategyInfo {
+        StrategyInfo::new(Self::NAME)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(self.clone())
+    }
+}
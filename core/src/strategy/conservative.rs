@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+
+use crate::observer::StrategyAction;
+use crate::places::BoardPlace;
+use crate::serialization::StrategyInfo;
+use crate::strategy::{
+    build_within, get_monopolized_colors, unmortgage_within, ExpensiveHousesProtectionStrategy,
+    GameView, Plan, PlayerStrategy,
+};
+
+///
+/// Holds the parameters of `ConservativeStrategy`.
+///
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConservativeParams {
+    pub reserve: u32,
+}
+
+impl Default for ConservativeParams {
+    fn default() -> Self {
+        ConservativeParams { reserve: 500 }
+    }
+}
+
+///
+/// Always keeps a fixed amount of cash, and invests only the rest.
+///
+/// It does not buy places which would break into the reserve.
+///
+#[derive(Clone)]
+pub struct ConservativeStrategy {
+    params: ConservativeParams,
+}
+
+impl ConservativeStrategy {
+    ///
+    /// The name of the strategy in `StrategyRegistry`.
+    ///
+    pub const NAME: &'static str = "conservative";
+
+    pub fn new_boxed(params: ConservativeParams) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(ConservativeStrategy { params })
+    }
+
+    ///
+    /// Unmortgages places and builds houses with the money beyond the reserve.
+    ///
+    fn invest_on(&self, plan: &mut Plan) {
+        let board = plan.board;
+        let player_id = plan.player_id;
+        if plan.get_money() <= self.params.reserve {
+            return;
+        }
+        let mut usable = plan.get_money() - self.params.reserve;
+
+        for color in get_monopolized_colors(board, player_id) {
+            if !unmortgage_within(plan, &mut usable, |place| place.get_color() == color) {
+                return;
+            }
+            if !build_within(plan, &color, 5, &mut usable) {
+                return;
+            }
+        }
+
+        unmortgage_within(plan, &mut usable, |_| true);
+    }
+}
+
+impl PlayerStrategy for ConservativeStrategy {
+    fn raise(&self, view: &GameView, debt: u32) -> Vec<StrategyAction> {
+        ExpensiveHousesProtectionStrategy::default().raise(view, debt)
+    }
+
+    fn invest(&self, view: &GameView) -> Vec<StrategyAction> {
+        let mut plan = Plan::new(view);
+        self.invest_on(&mut plan);
+        plan.into_actions()
+    }
+
+    fn buy(&self, view: &GameView, place_id: usize, price: u32) -> Vec<StrategyAction> {
+        if price + self.params.reserve <= view.get_money() {
+            vec![StrategyAction::Buy(place_id)]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn get_info(&self) -> StrategyInfo {
+        StrategyInfo::with_params(Self::NAME, &self.params)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,330 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::board::Board;
+use crate::observer::StrategyAction;
+use crate::places::{BoardColor, BoardPlace};
+use crate::serialization::StrategyInfo;
+use crate::strategy::{get_monopolized_colors, GameView, Plan, PlayerStrategy};
+
+///
+/// Determines how much money the player keeps in hand.
+///
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reserve {
+    /// Keeps the most expensive rent on the board multiplied by the value.
+    MostExpensive(f64),
+    /// Keeps the fixed amount of money.
+    Fixed(u32),
+}
+
+///
+/// Determines which mortgaged places the player unmortgages.
+///
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnmortgagePolicy {
+    /// Unmortgages railroads first, then monopolized colors, then the others.
+    All,
+    /// Unmortgages only places in monopolized colors.
+    MonopoliesOnly,
+    /// Never unmortgages places.
+    Never,
+}
+
+///
+/// Holds the parameters of `ExpensiveHousesProtectionStrategy`.
+///
+/// The colors in `color_priority` are built on first in the order, and liquidated last.
+/// The other colors are built on in descending order of the number of houses.
+///
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExpensiveHousesProtectionParams {
+    pub reserve: Reserve,
+    pub color_priority: Vec<BoardColor>,
+    pub unmortgage: UnmortgagePolicy,
+    pub max_houses: u8,
+}
+
+impl Default for ExpensiveHousesProtectionParams {
+    fn default() -> Self {
+        ExpensiveHousesProtectionParams {
+            reserve: Reserve::MostExpensive(1.0),
+            color_priority: Vec::new(),
+            unmortgage: UnmortgagePolicy::All,
+            max_houses: 5,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ExpensiveHousesProtectionStrategy {
+    params: ExpensiveHousesProtectionParams,
+}
+
+impl ExpensiveHousesProtectionStrategy {
+    ///
+    /// The name of the strategy in `StrategyRegistry`.
+    ///
+    pub const NAME: &'static str = "expensive_houses_protection";
+
+    pub fn new_boxed() -> Box<dyn PlayerStrategy + Send> {
+        Self::new_boxed_with(ExpensiveHousesProtectionParams::default())
+    }
+
+    pub fn new_boxed_with(
+        params: ExpensiveHousesProtectionParams,
+    ) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(Self::new(params))
+    }
+
+    pub fn new(params: ExpensiveHousesProtectionParams) -> Self {
+        assert!(params.max_houses <= 5);
+
+        ExpensiveHousesProtectionStrategy { params }
+    }
+
+    ///
+    /// Gets the money the player keeps in hand.
+    ///
+    fn get_reserve(&self, board: &Board, player_id: usize) -> u32 {
+        match self.params.reserve {
+            Reserve::MostExpensive(multiplier) => {
+                (board.get_most_expensive(player_id) as f64 * multiplier) as u32
+            }
+            Reserve::Fixed(reserve) => reserve,
+        }
+    }
+
+    ///
+    /// Gets the priority of the color. The smaller is the higher.
+    ///
+    fn get_priority(&self, color: &BoardColor) -> usize {
+        self.params
+            .color_priority
+            .iter()
+            .position(|prioritized| prioritized == color)
+            .unwrap_or(usize::MAX)
+    }
+
+    ///
+    /// Sells houses and mortgages places until the player can pay off the debt.
+    ///
+    fn raise_on(&self, debt: u32, plan: &mut Plan) {
+        let board = plan.board;
+        let player_id = plan.player_id;
+
+        let mut monopolized_color = HashSet::new();
+        for color in BoardColor::get_estate_colors() {
+            if board.get_monopolizer(color.clone()) == Some(player_id) {
+                monopolized_color.insert(color);
+            }
+        }
+
+        // Liquidates the colors with lower priority first.
+        let mut liquidation_order = BoardColor::get_estate_colors();
+        liquidation_order.sort_by_key(|color| Reverse(self.get_priority(color)));
+
+        let players_places = board.get_owned_places(player_id);
+
+        // Mortgages the places not monopolized.
+        let players_not_monopolized_places = players_places.iter().copied().filter(|&id| {
+            !monopolized_color.contains(&board.places[id].get_color()) && !plan.is_mortgaged(id)
+        });
+
+        for id in players_not_monopolized_places.collect::<Vec<_>>() {
+            plan.mortgage(id);
+
+            if plan.get_money() >= debt {
+                return;
+            }
+        }
+
+        let mut players_monopolized_places_with_houses = players_places
+            .iter()
+            .filter_map(|&id| {
+                if monopolized_color.contains(&board.places[id].get_color())
+                    && !plan.is_mortgaged(id)
+                {
+                    Some((plan.get_num_houses(id), id))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for color in liquidation_order.iter().cloned() {
+            let color_places = players_monopolized_places_with_houses
+                .iter()
+                .filter(|(_, id)| board.places[*id].get_color() == color)
+                .copied()
+                .collect::<Vec<_>>();
+            let sum_of_houses: u8 = color_places.iter().map(|(houses, _)| houses).sum();
+            if sum_of_houses == 0 {
+                for (_, id) in color_places {
+                    plan.mortgage(id);
+
+                    if plan.get_money() >= debt {
+                        return;
+                    }
+                }
+            }
+        }
+
+        players_monopolized_places_with_houses.retain(|(_, id)| !plan.is_mortgaged(*id));
+
+        for color in liquidation_order.iter().cloned() {
+            let mut color_places = players_monopolized_places_with_houses
+                .iter_mut()
+                .filter(|(_, id)| board.places[*id].get_color() == color)
+                .collect::<Vec<_>>();
+            let mut sum_of_houses: u8 = color_places.iter().map(|(houses, _)| houses).sum();
+
+            while sum_of_houses > 0 {
+                color_places.sort_by(|(houses1, _), (houses2, _)| houses2.cmp(houses1));
+
+                let (houses, id) = color_places.first_mut().unwrap();
+                plan.sell_house(*id);
+                *houses -= 1;
+
+                sum_of_houses -= 1;
+
+                if plan.get_money() >= debt {
+                    return;
+                }
+            }
+
+            // Mortgages the places. Prioritizes the cheaper place.
+            color_places.sort_by_key(|(_, id)| *id);
+            for (_, id) in color_places {
+                plan.mortgage(*id);
+
+                if plan.get_money() >= debt {
+                    return;
+                }
+            }
+        }
+    }
+
+    ///
+    /// Unmortgages places and builds houses with the money beyond the reserve.
+    ///
+    fn invest_on(&self, plan: &mut Plan) {
+        let board = plan.board;
+        let player_id = plan.player_id;
+        let usable = plan.get_money() as i64 - self.get_reserve(board, player_id) as i64;
+        if usable <= 0 {
+            return;
+        }
+        let mut usable = usable as u32;
+
+        if self.params.unmortgage == UnmortgagePolicy::All {
+            let mortgaged_railroads = plan
+                .get_unmortgageable_places()
+                .into_iter()
+                .filter(|&id| board.places[id].get_color() == BoardColor::Railroad);
+            for id in mortgaged_railroads.collect::<Vec<_>>() {
+                let cost = board.places[id].get_return_cost();
+                if cost > usable {
+                    return;
+                }
+                usable -= cost;
+
+                plan.unmortgage(id);
+            }
+        }
+
+        let mut players_colors = get_monopolized_colors(board, player_id);
+        players_colors.sort_by_key(|color| {
+            (
+                self.get_priority(color),
+                u8::MAX - plan.get_houses_num_by_color(color),
+            )
+        });
+
+        for color in players_colors {
+            let mut places = plan.get_ids_by_color(&color);
+            if self.params.unmortgage == UnmortgagePolicy::Never {
+                // Houses cannot be built on the color containing a mortgaged place.
+                if places.iter().any(|&id| plan.is_mortgaged(id)) {
+                    continue;
+                }
+            } else {
+                let mortgaged_places = places
+                    .iter()
+                    .copied()
+                    .filter(|&id| plan.is_mortgaged(id))
+                    .collect::<Vec<_>>();
+                for id in mortgaged_places {
+                    let cost = board.places[id].get_return_cost();
+                    if cost > usable {
+                        return;
+                    }
+                    usable -= cost;
+
+                    plan.unmortgage(id);
+                }
+            }
+
+            let houses_limit = places.len() as u8 * self.params.max_houses;
+            let mut houses = plan.get_houses_num_by_color(&color);
+            while houses < houses_limit {
+                places.sort_by_key(|&id| plan.get_num_houses(id));
+
+                let place_to_build = places[0];
+                let cost = board.places[place_to_build].get_price_of_house().unwrap();
+                if cost > usable {
+                    return;
+                }
+                usable -= cost;
+
+                plan.build_house(place_to_build);
+                houses += 1;
+            }
+        }
+
+        if self.params.unmortgage == UnmortgagePolicy::All {
+            for id in plan.get_unmortgageable_places() {
+                let cost = board.places[id].get_return_cost();
+                if cost > usable {
+                    return;
+                }
+                usable -= cost;
+
+                plan.unmortgage(id);
+            }
+        }
+    }
+}
+
+impl Default for ExpensiveHousesProtectionStrategy {
+    fn default() -> Self {
+        Self::new(ExpensiveHousesProtectionParams::default())
+    }
+}
+
+impl PlayerStrategy for ExpensiveHousesProtectionStrategy {
+    fn raise(&self, view: &GameView, debt: u32) -> Vec<StrategyAction> {
+        let mut plan = Plan::new(view);
+        self.raise_on(debt, &mut plan);
+        plan.into_actions()
+    }
+
+    fn invest(&self, view: &GameView) -> Vec<StrategyAction> {
+        let mut plan = Plan::new(view);
+        self.invest_on(&mut plan);
+        plan.into_actions()
+    }
+
+    fn get_info(&self) -> StrategyInfo {
+        StrategyInfo::with_params(Self::NAME, &self.params)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,265 @@
+use std::cell::RefCell;
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::thread::spawn;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::action::Decision;
+use crate::observer::{StrategyAction, TradeOffer};
+use crate::serialization::StrategyInfo;
+use crate::strategy::{decide, ExpensiveHousesProtectionStrategy, GameView, PlayerStrategy};
+
+///
+/// Holds the parameters of `ExternalStrategy`.
+///
+/// `timeout` is how long the bot can take for a decision in milliseconds.
+///
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExternalParams {
+    pub command: String,
+    pub args: Vec<String>,
+    pub timeout: u64,
+    pub fallback: StrategyInfo,
+}
+
+impl Default for ExternalParams {
+    fn default() -> Self {
+        ExternalParams {
+            command: String::new(),
+            args: Vec::new(),
+            timeout: 1000,
+            fallback: StrategyInfo::new(ExpensiveHousesProtectionStrategy::NAME),
+        }
+    }
+}
+
+///
+/// The response of a bot, which is shared with the other strategies deciding outside the process.
+///
+#[derive(Deserialize)]
+pub(crate) struct BotResponse {
+    pub actions: Vec<StrategyAction>,
+}
+
+///
+/// Makes the request for the decision sent to a bot.
+///
+pub(crate) fn get_request(view: &GameView, decision: &Decision) -> Value {
+    json!({
+        "player_id": view.player_id,
+        "decision": decision,
+        "game": view.to_info(),
+    })
+}
+
+///
+/// A running bot with the lines of its output.
+///
+struct Bot {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+}
+
+enum BotState {
+    NotStarted,
+    Running(Bot),
+    Failed,
+}
+
+///
+/// Asks a bot running as a subprocess for the decisions.
+///
+/// The bot receives a line of JSON `{"player_id", "decision", "game"}` on its stdin for every decision,
+/// and writes a line of JSON `{"actions"}` to its stdout.
+/// Once the bot fails to answer in time, exits, or answers actions which are not legal,
+/// it is killed and the fallback strategy decides for the rest of the game. The reason is reported to the session log.
+///
+pub struct ExternalStrategy {
+    params: ExternalParams,
+    fallback: Box<dyn PlayerStrategy + Send>,
+    bot: RefCell<BotState>,
+    reports: RefCell<Vec<String>>,
+}
+
+impl ExternalStrategy {
+    ///
+    /// The name of the strategy in `StrategyRegistry`.
+    ///
+    pub const NAME: &'static str = "external";
+
+    pub fn new_boxed(
+        params: ExternalParams,
+        fallback: Box<dyn PlayerStrategy + Send>,
+    ) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(ExternalStrategy {
+            params,
+            fallback,
+            bot: RefCell::new(BotState::NotStarted),
+            reports: RefCell::new(Vec::new()),
+        })
+    }
+
+    ///
+    /// Asks the bot for the decision, or the fallback strategy if the bot is not available.
+    ///
+    fn decide(&self, view: &GameView, decision: Decision) -> Vec<StrategyAction> {
+        let mut bot = self.bot.borrow_mut();
+        if let BotState::NotStarted = *bot {
+            *bot = match self.start() {
+                Ok(started) => BotState::Running(started),
+                Err(msg) => self.fail(msg),
+            };
+        }
+
+        let result = match &mut *bot {
+            BotState::Running(running) => self.ask(running, view, &decision),
+            _ => return decide(self.fallback.as_ref(), view, &decision),
+        };
+        match result {
+            Ok(actions) => actions,
+            Err(msg) => {
+                if let BotState::Running(mut running) = std::mem::replace(&mut *bot, self.fail(msg))
+                {
+                    let _ = running.child.kill();
+                    let _ = running.child.wait();
+                }
+                decide(self.fallback.as_ref(), view, &decision)
+            }
+        }
+    }
+
+    ///
+    /// Starts the bot, reading its output on another thread to wait for it with a timeout.
+    ///
+    fn start(&self) -> Result<Bot, String> {
+        let mut child = Command::new(&self.params.command)
+            .args(&self.params.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|err| format!("The bot cannot be started. {}", err))?;
+
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let (sender, lines) = channel();
+        spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Bot {
+            child,
+            stdin,
+            lines,
+        })
+    }
+
+    ///
+    /// Sends the decision to the bot and validates its response.
+    ///
+    fn ask(
+        &self,
+        bot: &mut Bot,
+        view: &GameView,
+        decision: &Decision,
+    ) -> Result<Vec<StrategyAction>, String> {
+        let request = get_request(view, decision);
+        writeln!(bot.stdin, "{}", request)
+            .and_then(|_| bot.stdin.flush())
+            .map_err(|err| format!("The bot cannot receive the request. {}", err))?;
+
+        let line = bot
+            .lines
+            .recv_timeout(Duration::from_millis(self.params.timeout))
+            .map_err(|err| match err {
+                RecvTimeoutError::Timeout => "The bot does not answer in time.".to_string(),
+                RecvTimeoutError::Disconnected => "The bot exits.".to_string(),
+            })?;
+        let response: BotResponse = serde_json::from_str(&line)
+            .map_err(|err| format!("The bot answers invalid JSON. {}", err))?;
+
+        view.validate_actions(decision, &response.actions)?;
+
+        Ok(response.actions)
+    }
+
+    ///
+    /// Reports why the bot fails, to be logged by the session.
+    ///
+    fn fail(&self, msg: String) -> BotState {
+        self.reports.borrow_mut().push(format!(
+            "{} Falls back to \"{}\".",
+            msg, self.params.fallback.name
+        ));
+        BotState::Failed
+    }
+}
+
+impl Drop for ExternalStrategy {
+    fn drop(&mut self) {
+        if let BotState::Running(bot) = self.bot.get_mut() {
+            let _ = bot.child.kill();
+            let _ = bot.child.wait();
+        }
+    }
+}
+
+impl PlayerStrategy for ExternalStrategy {
+    fn raise(&self, view: &GameView, debt: u32) -> Vec<StrategyAction> {
+        self.decide(view, Decision::Raise(debt))
+    }
+
+    fn invest(&self, view: &GameView) -> Vec<StrategyAction> {
+        self.decide(view, Decision::Invest)
+    }
+
+    fn buy(&self, view: &GameView, place_id: usize, price: u32) -> Vec<StrategyAction> {
+        self.decide(view, Decision::Buy(place_id, price))
+    }
+
+    fn leave_jail(&self, view: &GameView) -> Vec<StrategyAction> {
+        self.decide(view, Decision::LeaveJail)
+    }
+
+    fn accepts_trade(&self, view: &GameView, proposer: usize, offer: &TradeOffer) -> bool {
+        self.fallback.accepts_trade(view, proposer, offer)
+    }
+
+    fn get_info(&self) -> StrategyInfo {
+        StrategyInfo::with_params(Self::NAME, &self.params)
+    }
+
+    fn take_reports(&self) -> Vec<String> {
+        self.reports.take()
+    }
+
+    fn set_seed(&self, seed: u64) {
+        self.fallback.set_seed(seed);
+    }
+
+    fn clone_boxed(&self) -> Box<dyn PlayerStrategy + Send> {
+        // A running bot cannot be shared, so the clone starts its own one unless the bot has failed.
+        let bot = match *self.bot.borrow() {
+            BotState::Failed => BotState::Failed,
+            _ => BotState::NotStarted,
+        };
+        Box::new(ExternalStrategy {
+            params: self.params.clone(),
+            fallback: self.fallback.clone(),
+            bot: RefCell::new(bot),
+            reports: RefCell::new(Vec::new()),
+        })
+    }
+}
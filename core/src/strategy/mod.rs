@@ -0,0 +1,194 @@
+use crate::action::Decision;
+use crate::board::Board;
+use crate::observer::{StrategyAction, TradeOffer};
+use crate::places::{BoardColor, BoardPlace, Place};
+use crate::serialization::StrategyInfo;
+
+///
+/// Determines the player's behavior.
+///
+/// A strategy sees the game through a read-only `GameView` and returns the actions to take.
+/// The game validates and applies them in order, and reports the illegal ones as errors.
+///
+pub trait PlayerStrategy {
+    ///
+    /// Decides the actions to raise money to pay off the debt.
+    ///
+    /// This is called only when the player does not have enough money in hand.
+    /// If they still cannot pay after the actions, they get bankrupted.
+    ///
+    fn raise(&self, view: &GameView, debt: u32) -> Vec<StrategyAction>;
+
+    ///
+    /// Decides the actions to invest at the end of a turn.
+    ///
+    fn invest(&self, view: &GameView) -> Vec<StrategyAction>;
+
+    ///
+    /// Decides the actions when the player lands on the place nobody owns.
+    ///
+    /// The player buys the place if the actions contain `StrategyAction::Buy` of it, which is applied after the others.
+    /// Note that the player who cannot afford the place gets bankrupted. Buys every place by default.
+    ///
+    fn buy(&self, _view: &GameView, place_id: usize, _price: u32) -> Vec<StrategyAction> {
+        vec![StrategyAction::Buy(place_id)]
+    }
+
+    ///
+    /// Decides the actions before rolling dices in the jail.
+    ///
+    /// The player pays the fee and leaves the jail if the actions contain `StrategyAction::PayJailFee`.
+    /// Stays in the jail by default.
+    ///
+    fn leave_jail(&self, _view: &GameView) -> Vec<StrategyAction> {
+        Vec::new()
+    }
+
+    ///
+    /// Decides whether to accept the trade offered by the proposer. Declines every offer by default.
+    ///
+    fn accepts_trade(&self, _view: &GameView, _proposer: usize, _offer: &TradeOffer) -> bool {
+        false
+    }
+
+    ///
+    /// Gets the name and the parameters of the strategy, which can be passed to `StrategyRegistry`.
+    ///
+    fn get_info(&self) -> StrategyInfo;
+
+    ///
+    /// Takes the messages reported since the last call, such as falling back to another strategy.
+    ///
+    /// The session logs them after every decision. Reports nothing by default.
+    ///
+    fn take_reports(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    ///
+    /// Reseeds the random number generators of the strategy, if any. Does nothing by default.
+    ///
+    fn set_seed(&self, _seed: u64) {}
+
+    ///
+    /// Clones the strategy with its state, such as the state of its random number generator.
+    ///
+    fn clone_boxed(&self) -> Box<dyn PlayerStrategy + Send>;
+}
+
+impl Clone for Box<dyn PlayerStrategy + Send> {
+    fn clone(&self) -> Self {
+        self.clone_boxed()
+    }
+}
+
+pub mod aggressive_builder;
+pub mod conservative;
+pub mod expensive_houses_protection;
+pub mod external;
+pub mod monte_carlo;
+pub mod never_buy_above;
+mod plan;
+pub mod railroads_and_utilities;
+pub mod random;
+pub mod registry;
+pub mod risk_aware;
+pub mod three_houses_first;
+pub mod view;
+pub mod webhook;
+
+pub use crate::strategy::aggressive_builder::AggressiveBuilderStrategy;
+pub use crate::strategy::conservative::ConservativeStrategy;
+pub use crate::strategy::expensive_houses_protection::ExpensiveHousesProtectionStrategy;
+pub use crate::strategy::external::ExternalStrategy;
+pub use crate::strategy::monte_carlo::MonteCarloStrategy;
+pub use crate::strategy::never_buy_above::NeverBuyAboveStrategy;
+pub(crate) use crate::strategy::plan::Plan;
+pub use crate::strategy::railroads_and_utilities::RailroadsAndUtilitiesStrategy;
+pub use crate::strategy::random::RandomStrategy;
+pub use crate::strategy::registry::StrategyRegistry;
+pub use crate::strategy::risk_aware::RiskAwareStrategy;
+pub use crate::strategy::three_houses_first::ThreeHousesFirstStrategy;
+pub use crate::strategy::view::GameView;
+pub use crate::strategy::webhook::WebhookStrategy;
+
+///
+/// Asks the strategy for the actions on the decision.
+///
+pub(crate) fn decide(
+    strategy: &dyn PlayerStrategy,
+    view: &GameView,
+    decision: &Decision,
+) -> Vec<StrategyAction> {
+    match *decision {
+        Decision::Raise(debt) => strategy.raise(view, debt),
+        Decision::Invest => strategy.invest(view),
+        Decision::Buy(place_id, price) => strategy.buy(view, place_id, price),
+        Decision::LeaveJail => strategy.leave_jail(view),
+    }
+}
+
+///
+/// Unmortgages the player's places which satisfy the condition within the budget.
+///
+/// Returns `false` if the budget runs out.
+///
+pub(crate) fn unmortgage_within<F>(plan: &mut Plan, usable: &mut u32, condition: F) -> bool
+where
+    F: Fn(&Place) -> bool,
+{
+    let places = plan
+        .get_unmortgageable_places()
+        .into_iter()
+        .filter(|&id| condition(&plan.board.places[id]))
+        .collect::<Vec<_>>();
+    for id in places {
+        let cost = plan.board.places[id].get_return_cost();
+        if cost > *usable {
+            return false;
+        }
+        *usable -= cost;
+
+        plan.unmortgage(id);
+    }
+    true
+}
+
+///
+/// Builds houses on the color flatly within the budget, up to `max_houses` on each place.
+///
+/// Returns `false` if the budget runs out.
+///
+pub(crate) fn build_within(
+    plan: &mut Plan,
+    color: &BoardColor,
+    max_houses: u8,
+    usable: &mut u32,
+) -> bool {
+    loop {
+        let place_id = plan.get_buildable_places().into_iter().find(|&id| {
+            plan.board.places[id].get_color() == *color && plan.get_num_houses(id) < max_houses
+        });
+        let Some(place_id) = place_id else {
+            return true;
+        };
+
+        let cost = plan.board.places[place_id].get_price_of_house().unwrap();
+        if cost > *usable {
+            return false;
+        }
+        *usable -= cost;
+
+        plan.build_house(place_id);
+    }
+}
+
+///
+/// Gets the colors monopolized by the player.
+///
+pub(crate) fn get_monopolized_colors(board: &Board, player_id: usize) -> Vec<BoardColor> {
+    BoardColor::get_estate_colors()
+        .into_iter()
+        .filter(|color| board.get_monopolizer(color.clone()) == Some(player_id))
+        .collect()
+}
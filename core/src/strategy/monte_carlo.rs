@@ -0,0 +1,71 @@
+use std::cell::RefCell;
+
+use crate::action::Decision;
+use crate::advisor::{Advisor, AdvisorParams};
+use crate::observer::StrategyAction;
+use crate::serialization::StrategyInfo;
+use crate::strategy::{GameView, PlayerStrategy};
+
+///
+/// Takes the actions which `Advisor` ranks first.
+///
+/// Note that this is much slower than the other strategies, since every decision runs rollouts.
+///
+#[derive(Clone)]
+pub struct MonteCarloStrategy {
+    params: AdvisorParams,
+    advisor: RefCell<Advisor>,
+}
+
+impl MonteCarloStrategy {
+    ///
+    /// The name of the strategy in `StrategyRegistry`.
+    ///
+    pub const NAME: &'static str = "monte_carlo";
+
+    pub fn new_boxed(params: AdvisorParams) -> Box<dyn PlayerStrategy + Send> {
+        let advisor = RefCell::new(Advisor::new(params.clone()));
+        Box::new(MonteCarloStrategy { params, advisor })
+    }
+
+    ///
+    /// Gets the best actions for the decision.
+    ///
+    fn decide(&self, view: &GameView, decision: Decision) -> Vec<StrategyAction> {
+        let ranked = self.advisor.borrow_mut().advise(view, &decision);
+        ranked
+            .ok()
+            .and_then(|ranked| ranked.into_iter().next())
+            .map_or_else(Vec::new, |ranked| ranked.actions)
+    }
+}
+
+impl PlayerStrategy for MonteCarloStrategy {
+    fn raise(&self, view: &GameView, debt: u32) -> Vec<StrategyAction> {
+        self.decide(view, Decision::Raise(debt))
+    }
+
+    fn invest(&self, view: &GameView) -> Vec<StrategyAction> {
+        self.decide(view, Decision::Invest)
+    }
+
+    fn buy(&self, view: &GameView, place_id: usize, price: u32) -> Vec<StrategyAction> {
+        self.decide(view, Decision::Buy(place_id, price))
+    }
+
+    fn leave_jail(&self, view: &GameView) -> Vec<StrategyAction> {
+        self.decide(view, Decision::LeaveJail)
+    }
+
+    fn get_info(&self) -> StrategyInfo {
+        StrategyInfo::with_params(Self::NAME, &self.params)
+    }
+
+    fn set_seed(&self, seed: u64) {
+        self.advisor.borrow_mut().set_seed(seed);
+    }
+
+    fn clone_boxed(&self) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(self.clone())
+    }
+}
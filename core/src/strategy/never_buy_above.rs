@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+use crate::observer::StrategyAction;
+use crate::serialization::StrategyInfo;
+use crate::strategy::{ExpensiveHousesProtectionStrategy, GameView, PlayerStrategy};
+
+///
+/// Holds the parameters of `NeverBuyAboveStrategy`.
+///
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NeverBuyAboveParams {
+    pub limit: u32,
+}
+
+impl Default for NeverBuyAboveParams {
+    fn default() -> Self {
+        NeverBuyAboveParams { limit: 200 }
+    }
+}
+
+///
+/// Never buys places priced above the limit.
+///
+/// Otherwise, it behaves as `ExpensiveHousesProtectionStrategy`.
+///
+#[derive(Clone)]
+pub struct NeverBuyAboveStrategy {
+    params: NeverBuyAboveParams,
+}
+
+impl NeverBuyAboveStrategy {
+    ///
+    /// The name of the strategy in `StrategyRegistry`.
+    ///
+    pub const NAME: &'static str = "never_buy_above";
+
+    pub fn new_boxed(params: NeverBuyAboveParams) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(NeverBuyAboveStrategy { params })
+    }
+}
+
+impl PlayerStrategy for NeverBuyAboveStrategy {
+    fn raise(&self, view: &GameView, debt: u32) -> Vec<StrategyAction> {
+        ExpensiveHousesProtectionStrategy::default().raise(view, debt)
+    }
+
+    fn invest(&self, view: &GameView) -> Vec<StrategyAction> {
+        ExpensiveHousesProtectionStrategy::default().invest(view)
+    }
+
+    fn buy(&self, view: &GameView, place_id: usize, price: u32) -> Vec<StrategyAction> {
+        if price <= self.params.limit && price <= view.get_money() {
+            vec![StrategyAction::Buy(place_id)]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn get_info(&self) -> StrategyInfo {
+        StrategyInfo::with_params(Self::NAME, &self.params)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,225 @@
+use crate::board::Board;
+use crate::observer::StrategyAction;
+use crate::places::{BoardColor, BoardPlace};
+use crate::strategy::{get_monopolized_colors, GameView};
+
+///
+/// Records the actions of a player, tracking the houses, the mortgages and the money they change.
+///
+/// The board of the view is never copied. Only the states changed by the actions are held over it,
+/// and the rules of the game are checked against them, so that the actions can be applied in the order taken.
+///
+pub(crate) struct Plan<'a> {
+    pub board: &'a Board,
+    pub player_id: usize,
+    money: u32,
+    houses: Vec<(usize, u8)>,
+    mortgaged: Vec<(usize, bool)>,
+    actions: Vec<StrategyAction>,
+}
+
+impl<'a> Plan<'a> {
+    ///
+    /// Starts a plan of the player of the view.
+    ///
+    pub fn new(view: &GameView<'a>) -> Self {
+        Plan {
+            board: view.board,
+            player_id: view.player_id,
+            money: view.get_money(),
+            houses: Vec::new(),
+            mortgaged: Vec::new(),
+            actions: Vec::new(),
+        }
+    }
+
+    ///
+    /// Gets the money of the player after the actions so far.
+    ///
+    pub fn get_money(&self) -> u32 {
+        self.money
+    }
+
+    ///
+    /// Gets the number of houses on the place after the actions so far. Returns 0 for the places other than estates.
+    ///
+    pub fn get_num_houses(&self, place_id: usize) -> u8 {
+        match self.houses.iter().find(|(id, _)| *id == place_id) {
+            Some(&(_, houses)) => houses,
+            None => self.board.places[place_id].get_num_houses().unwrap_or(0),
+        }
+    }
+
+    ///
+    /// Gets whether the place is mortgaged after the actions so far.
+    ///
+    pub fn is_mortgaged(&self, place_id: usize) -> bool {
+        match self.mortgaged.iter().find(|(id, _)| *id == place_id) {
+            Some(&(_, mortgaged)) => mortgaged,
+            None => self.board.places[place_id].is_mortgaged(),
+        }
+    }
+
+    ///
+    /// Gets ids of the places with the designated color.
+    ///
+    pub fn get_ids_by_color(&self, color: &BoardColor) -> Vec<usize> {
+        self.board
+            .gets_by_color(color.clone())
+            .map(|place| place.get_id())
+            .collect()
+    }
+
+    ///
+    /// Gets the sum of the numbers of houses which stand on the designated color.
+    ///
+    pub fn get_houses_num_by_color(&self, color: &BoardColor) -> u8 {
+        self.get_ids_by_color(color)
+            .into_iter()
+            .map(|id| self.get_num_houses(id))
+            .sum()
+    }
+
+    ///
+    /// Gets ids of the places on which the player can build a house under the rule of building flatly.
+    ///
+    /// Money is not taken into account.
+    ///
+    pub fn get_buildable_places(&self) -> Vec<usize> {
+        let mut buildable = Vec::new();
+        for color in get_monopolized_colors(self.board, self.player_id) {
+            let places = self.get_ids_by_color(&color);
+            if places.iter().any(|&id| self.is_mortgaged(id)) {
+                continue;
+            }
+
+            let min_houses = places
+                .iter()
+                .map(|&id| self.get_num_houses(id))
+                .min()
+                .unwrap();
+            buildable.extend(places.into_iter().filter(|&id| {
+                let houses = self.get_num_houses(id);
+                houses == min_houses && houses < 5
+            }));
+        }
+        buildable
+    }
+
+    ///
+    /// Gets ids of the places from which the player can sell a house under the rule of building flatly.
+    ///
+    pub fn get_sellable_places(&self) -> Vec<usize> {
+        let mut sellable = Vec::new();
+        for color in get_monopolized_colors(self.board, self.player_id) {
+            let places = self.get_ids_by_color(&color);
+            let max_houses = places
+                .iter()
+                .map(|&id| self.get_num_houses(id))
+                .max()
+                .unwrap();
+            if max_houses > 0 {
+                sellable.extend(
+                    places
+                        .into_iter()
+                        .filter(|&id| self.get_num_houses(id) == max_houses),
+                );
+            }
+        }
+        sellable
+    }
+
+    ///
+    /// Gets ids of the places the player can mortgage.
+    ///
+    /// A place cannot be mortgaged while any place of the same color has houses.
+    ///
+    pub fn get_mortgageable_places(&self) -> Vec<usize> {
+        self.board
+            .get_owned_places(self.player_id)
+            .iter()
+            .copied()
+            .filter(|&id| !self.is_mortgaged(id))
+            .filter(|&id| {
+                let place = &self.board.places[id];
+                !place.is_estate() || self.get_houses_num_by_color(&place.get_color()) == 0
+            })
+            .collect()
+    }
+
+    ///
+    /// Gets ids of the places the player can unmortgage.
+    ///
+    /// Money is not taken into account.
+    ///
+    pub fn get_unmortgageable_places(&self) -> Vec<usize> {
+        self.board
+            .get_owned_places(self.player_id)
+            .iter()
+            .copied()
+            .filter(|&id| self.is_mortgaged(id))
+            .collect()
+    }
+
+    ///
+    /// Builds a house on the place.
+    ///
+    pub fn build_house(&mut self, place_id: usize) {
+        assert!(self.get_buildable_places().contains(&place_id));
+        let cost = self.board.places[place_id].get_price_of_house().unwrap();
+        self.money -= cost;
+        self.set_num_houses(place_id, self.get_num_houses(place_id) + 1);
+        self.actions.push(StrategyAction::BuildHouse(place_id));
+    }
+
+    ///
+    /// Sells a house on the place.
+    ///
+    pub fn sell_house(&mut self, place_id: usize) {
+        assert!(self.get_sellable_places().contains(&place_id));
+        self.money += self.board.places[place_id].get_price_of_house().unwrap() / 2;
+        self.set_num_houses(place_id, self.get_num_houses(place_id) - 1);
+        self.actions.push(StrategyAction::SellHouse(place_id));
+    }
+
+    ///
+    /// Mortgages the place.
+    ///
+    pub fn mortgage(&mut self, place_id: usize) {
+        assert!(self.get_mortgageable_places().contains(&place_id));
+        self.money += self.board.places[place_id].get_price() / 2;
+        self.set_mortgaged(place_id, true);
+        self.actions.push(StrategyAction::Mortgage(place_id));
+    }
+
+    ///
+    /// Unmortgages the place.
+    ///
+    pub fn unmortgage(&mut self, place_id: usize) {
+        assert!(self.get_unmortgageable_places().contains(&place_id));
+        self.money -= self.board.places[place_id].get_return_cost();
+        self.set_mortgaged(place_id, false);
+        self.actions.push(StrategyAction::Unmortgage(place_id));
+    }
+
+    ///
+    /// Finishes the plan, and gets the actions in the order taken.
+    ///
+    pub fn into_actions(self) -> Vec<StrategyAction> {
+        self.actions
+    }
+
+    fn set_num_houses(&mut self, place_id: usize, houses: u8) {
+        match self.houses.iter_mut().find(|(id, _)| *id == place_id) {
+            Some((_, current)) => *current = houses,
+            None => self.houses.push((place_id, houses)),
+        }
+    }
+
+    fn set_mortgaged(&mut self, place_id: usize, mortgaged: bool) {
+        match self.mortgaged.iter_mut().find(|(id, _)| *id == place_id) {
+            Some((_, current)) => *current = mortgaged,
+            None => self.mortgaged.push((place_id, mortgaged)),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+
+use crate::observer::StrategyAction;
+use crate::places::{BoardColor, BoardPlace};
+use crate::serialization::StrategyInfo;
+use crate::strategy::{
+    build_within, get_monopolized_colors, unmortgage_within, ExpensiveHousesProtectionStrategy,
+    GameView, Plan, PlayerStrategy,
+};
+
+///
+/// Holds the parameters of `RailroadsAndUtilitiesStrategy`.
+///
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RailroadsAndUtilitiesParams {
+    pub reserve: u32,
+}
+
+impl Default for RailroadsAndUtilitiesParams {
+    fn default() -> Self {
+        RailroadsAndUtilitiesParams { reserve: 200 }
+    }
+}
+
+///
+/// Collects railroads and utilities, which need no houses to earn rent.
+///
+/// It buys other places only if it can keep the reserve after that.
+///
+#[derive(Clone)]
+pub struct RailroadsAndUtilitiesStrategy {
+    params: RailroadsAndUtilitiesParams,
+}
+
+impl RailroadsAndUtilitiesStrategy {
+    ///
+    /// The name of the strategy in `StrategyRegistry`.
+    ///
+    pub const NAME: &'static str = "railroads_and_utilities";
+
+    pub fn new_boxed(params: RailroadsAndUtilitiesParams) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(RailroadsAndUtilitiesStrategy { params })
+    }
+
+    ///
+    /// Unmortgages places and builds houses with the money beyond the reserve.
+    ///
+    fn invest_on(&self, plan: &mut Plan) {
+        let board = plan.board;
+        let player_id = plan.player_id;
+        if plan.get_money() <= self.params.reserve {
+            return;
+        }
+        let mut usable = plan.get_money() - self.params.reserve;
+
+        if !unmortgage_within(plan, &mut usable, |place| {
+            matches!(
+                place.get_color(),
+                BoardColor::Railroad | BoardColor::Utilities
+            )
+        }) {
+            return;
+        }
+
+        for color in get_monopolized_colors(board, player_id) {
+            if !unmortgage_within(plan, &mut usable, |place| place.get_color() == color) {
+                return;
+            }
+            if !build_within(plan, &color, 5, &mut usable) {
+                return;
+            }
+        }
+
+        unmortgage_within(plan, &mut usable, |_| true);
+    }
+}
+
+impl PlayerStrategy for RailroadsAndUtilitiesStrategy {
+    fn raise(&self, view: &GameView, debt: u32) -> Vec<StrategyAction> {
+        ExpensiveHousesProtectionStrategy::default().raise(view, debt)
+    }
+
+    fn invest(&self, view: &GameView) -> Vec<StrategyAction> {
+        let mut plan = Plan::new(view);
+        self.invest_on(&mut plan);
+        plan.into_actions()
+    }
+
+    fn buy(&self, view: &GameView, place_id: usize, price: u32) -> Vec<StrategyAction> {
+        let reserve = match view.board.places[place_id].get_color() {
+            BoardColor::Railroad | BoardColor::Utilities => 0,
+            _ => self.params.reserve,
+        };
+        if price + reserve <= view.get_money() {
+            vec![StrategyAction::Buy(place_id)]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn get_info(&self) -> StrategyInfo {
+        StrategyInfo::with_params(Self::NAME, &self.params)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(self.clone())
+    }
+}
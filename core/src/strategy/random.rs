@@ -0,0 +1,156 @@
+use std::cell::RefCell;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::action::Decision;
+use crate::board::JAIL_FEE;
+use crate::observer::StrategyAction;
+use crate::places::BoardPlace;
+use crate::serialization::StrategyInfo;
+use crate::strategy::{GameView, Plan, PlayerStrategy};
+
+///
+/// Holds the parameters of `RandomStrategy`.
+///
+/// If `seed` is `None`, a random seed is used.
+///
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RandomParams {
+    pub seed: Option<u64>,
+}
+
+///
+/// Chooses one of the legal moves at random. Useful as a baseline.
+///
+#[derive(Clone)]
+pub struct RandomStrategy {
+    params: RandomParams,
+    rng: RefCell<StdRng>,
+}
+
+impl RandomStrategy {
+    ///
+    /// The name of the strategy in `StrategyRegistry`.
+    ///
+    pub const NAME: &'static str = "random";
+
+    pub fn new_boxed(params: RandomParams) -> Box<dyn PlayerStrategy + Send> {
+        let rng = StdRng::seed_from_u64(params.seed.unwrap_or_else(rand::random));
+        Box::new(RandomStrategy {
+            params,
+            rng: RefCell::new(rng),
+        })
+    }
+
+    ///
+    /// Sells houses and mortgages places at random until the player can pay off the debt.
+    ///
+    fn raise_on(&self, debt: u32, plan: &mut Plan) {
+        let mut rng = self.rng.borrow_mut();
+        while plan.get_money() < debt {
+            let sellable = plan.get_sellable_places();
+            let mortgageable = plan.get_mortgageable_places();
+
+            let candidates = sellable
+                .iter()
+                .map(|&id| (id, true))
+                .chain(mortgageable.iter().map(|&id| (id, false)))
+                .collect::<Vec<_>>();
+            let Some(&(place_id, sells)) = candidates.choose(&mut *rng) else {
+                return;
+            };
+
+            if sells {
+                plan.sell_house(place_id);
+            } else {
+                plan.mortgage(place_id);
+            }
+        }
+    }
+
+    ///
+    /// Builds houses and unmortgages places at random within the money.
+    ///
+    fn invest_on(&self, plan: &mut Plan) {
+        let board = plan.board;
+        let mut rng = self.rng.borrow_mut();
+
+        // Stops at random, regarding doing nothing as one of the legal moves.
+        loop {
+            let money = plan.get_money();
+            let buildable = plan
+                .get_buildable_places()
+                .into_iter()
+                .filter(|&id| board.places[id].get_price_of_house().unwrap() <= money);
+            let unmortgageable = plan
+                .get_unmortgageable_places()
+                .into_iter()
+                .filter(|&id| board.places[id].get_return_cost() <= money);
+
+            let candidates = buildable
+                .map(|id| (id, true))
+                .chain(unmortgageable.map(|id| (id, false)))
+                .collect::<Vec<_>>();
+            if rng.gen_range(0..(candidates.len() + 1)) == candidates.len() {
+                return;
+            }
+            let (place_id, builds) = *candidates.choose(&mut *rng).unwrap();
+
+            if builds {
+                plan.build_house(place_id);
+            } else {
+                plan.unmortgage(place_id);
+            }
+        }
+    }
+}
+
+impl PlayerStrategy for RandomStrategy {
+    fn raise(&self, view: &GameView, debt: u32) -> Vec<StrategyAction> {
+        let mut plan = Plan::new(view);
+        self.raise_on(debt, &mut plan);
+        plan.into_actions()
+    }
+
+    fn invest(&self, view: &GameView) -> Vec<StrategyAction> {
+        let mut plan = Plan::new(view);
+        self.invest_on(&mut plan);
+        plan.into_actions()
+    }
+
+    fn buy(&self, view: &GameView, place_id: usize, price: u32) -> Vec<StrategyAction> {
+        if price <= view.get_money() && self.rng.borrow_mut().gen_bool(0.5) {
+            vec![StrategyAction::Buy(place_id)]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn leave_jail(&self, view: &GameView) -> Vec<StrategyAction> {
+        let legal_actions = view.get_legal_actions(&Decision::LeaveJail);
+        if legal_actions.contains(&StrategyAction::PayJailFee)
+            && JAIL_FEE <= view.get_money()
+            && self.rng.borrow_mut().gen_bool(0.5)
+        {
+            vec![StrategyAction::PayJailFee]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn get_info(&self) -> StrategyInfo {
+        StrategyInfo::with_params(Self::NAME, &self.params)
+    }
+
+    fn set_seed(&self, seed: u64) {
+        *self.rng.borrow_mut() = StdRng::seed_from_u64(seed);
+    }
+
+    fn clone_boxed(&self) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::advisor::AdvisorParams;
+use crate::serialization::StrategyInfo;
+use crate::strategy::expensive_houses_protection::ExpensiveHousesProtectionParams;
+use crate::strategy::external::ExternalParams;
+use crate::strategy::webhook::WebhookParams;
+use crate::strategy::{
+    AggressiveBuilderStrategy, ConservativeStrategy, ExpensiveHousesProtectionStrategy,
+    ExternalStrategy, MonteCarloStrategy, NeverBuyAboveStrategy, PlayerStrategy,
+    RailroadsAndUtilitiesStrategy, RandomStrategy, RiskAwareStrategy, ThreeHousesFirstStrategy,
+    WebhookStrategy,
+};
+
+///
+/// Constructs a strategy from its parameters.
+///
+pub type StrategyConstructor = fn(&Value) -> Result<Box<dyn PlayerStrategy + Send>, String>;
+
+///
+/// Parses the parameters of a strategy. `null` is regarded as the default parameters.
+///
+pub fn parse_params<T: DeserializeOwned + Default>(params: &Value) -> Result<T, String> {
+    if params.is_null() {
+        Ok(T::default())
+    } else {
+        serde_json::from_value(params.clone()).map_err(|err| err.to_string())
+    }
+}
+
+///
+/// The registry installed by `StrategyRegistry::install`.
+///
+static INSTALLED: OnceLock<StrategyRegistry> = OnceLock::new();
+
+///
+/// Holds named strategies which can be constructed with parameters.
+///
+pub struct StrategyRegistry {
+    constructors: HashMap<String, StrategyConstructor>,
+}
+
+impl Default for StrategyRegistry {
+    ///
+    /// Generates a registry containing the built-in strategies.
+    ///
+    /// The strategies starting processes or sending requests are not contained.
+    ///
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(ExpensiveHousesProtectionStrategy::NAME, |params| {
+            let params: ExpensiveHousesProtectionParams = parse_params(params)?;
+            if params.max_houses > 5 {
+                return Err("\"max_houses\" must be 5 or less.".to_string());
+            }
+            Ok(ExpensiveHousesProtectionStrategy::new_boxed_with(params))
+        });
+        registry.register(AggressiveBuilderStrategy::NAME, |_| {
+            Ok(AggressiveBuilderStrategy::new_boxed())
+        });
+        registry.register(ConservativeStrategy::NAME, |params| {
+            Ok(ConservativeStrategy::new_boxed(parse_params(params)?))
+        });
+        registry.register(ThreeHousesFirstStrategy::NAME, |_| {
+            Ok(ThreeHousesFirstStrategy::new_boxed())
+        });
+        registry.register(RailroadsAndUtilitiesStrategy::NAME, |params| {
+            Ok(RailroadsAndUtilitiesStrategy::new_boxed(parse_params(
+                params,
+            )?))
+        });
+        registry.register(NeverBuyAboveStrategy::NAME, |params| {
+            Ok(NeverBuyAboveStrategy::new_boxed(parse_params(params)?))
+        });
+        registry.register(RandomStrategy::NAME, |params| {
+            Ok(RandomStrategy::new_boxed(parse_params(params)?))
+        });
+        registry.register(MonteCarloStrategy::NAME, |params| {
+            let params: AdvisorParams = parse_params(params)?;
+            if params.rollout.name == MonteCarloStrategy::NAME {
+                return Err(
+                    "The rollout strategy cannot be the Monte Carlo one itself.".to_string()
+                );
+            }
+            Ok(MonteCarloStrategy::new_boxed(params))
+        });
+        registry.register(RiskAwareStrategy::NAME, |params| {
+            Ok(RiskAwareStrategy::new_boxed(parse_params(params)?))
+        });
+        registry
+    }
+}
+
+impl StrategyRegistry {
+    ///
+    /// Generates a registry containing no strategy.
+    ///
+    pub fn empty() -> Self {
+        StrategyRegistry {
+            constructors: HashMap::new(),
+        }
+    }
+
+    ///
+    /// Generates a registry containing the built-in strategies and the ones delegating decisions outside,
+    /// `ExternalStrategy` and `WebhookStrategy`.
+    ///
+    /// Since they start any command and send requests to any URL, use this only where the games are trusted,
+    /// such as the CLI. Never use this for games received over a network.
+    ///
+    pub fn with_external() -> Self {
+        let mut registry = Self::default();
+        registry.register(ExternalStrategy::NAME, |params| {
+            let params: ExternalParams = parse_params(params)?;
+            if params.command.is_empty() {
+                return Err("\"command\" of the bot is required.".to_string());
+            }
+            let fallback = StrategyRegistry::get_builtin().create(&params.fallback)?;
+            Ok(ExternalStrategy::new_boxed(params, fallback))
+        });
+        registry.register(WebhookStrategy::NAME, |params| {
+            let params: WebhookParams = parse_params(params)?;
+            if params.url.is_empty() {
+                return Err("\"url\" of the service is required.".to_string());
+            }
+            let fallback = StrategyRegistry::get_builtin().create(&params.fallback)?;
+            Ok(WebhookStrategy::new_boxed(params, fallback))
+        });
+        registry
+    }
+
+    ///
+    /// Gets the shared registry containing the built-in strategies.
+    ///
+    pub fn get_builtin() -> &'static StrategyRegistry {
+        static BUILTIN: OnceLock<StrategyRegistry> = OnceLock::new();
+        BUILTIN.get_or_init(StrategyRegistry::default)
+    }
+
+    ///
+    /// Gets the registry which the games look up the strategies in.
+    ///
+    /// This is the built-in registry unless another one is installed by `StrategyRegistry::install`.
+    ///
+    pub fn get_installed() -> &'static StrategyRegistry {
+        INSTALLED.get_or_init(StrategyRegistry::default)
+    }
+
+    ///
+    /// Installs the registry which the games look up the strategies in.
+    ///
+    /// This has to be called once at the start of the program, before any game is constructed.
+    ///
+    pub fn install(registry: StrategyRegistry) -> Result<(), String> {
+        INSTALLED
+            .set(registry)
+            .map_err(|_| "A registry is already installed.".to_string())
+    }
+
+    ///
+    /// Registers a strategy. If the name is already used, the strategy is replaced.
+    ///
+    pub fn register(&mut self, name: &str, constructor: StrategyConstructor) {
+        self.constructors.insert(name.to_string(), constructor);
+    }
+
+    ///
+    /// Unregisters a strategy. Nothing happens if the name is not used.
+    ///
+    pub fn unregister(&mut self, name: &str) {
+        self.constructors.remove(name);
+    }
+
+    ///
+    /// Gets the names of the strategies registered.
+    ///
+    pub fn get_names(&self) -> Vec<&str> {
+        let mut names = self
+            .constructors
+            .keys()
+            .map(|name| name.as_str())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    ///
+    /// Constructs the strategy described by `info`.
+    ///
+    pub fn create(&self, info: &StrategyInfo) -> Result<Box<dyn PlayerStrategy + Send>, String> {
+        let constructor = self
+            .constructors
+            .get(&info.name)
+            .ok_or_else(|| format!("The strategy \"{}\" is not registered.", info.name))?;
+        constructor(&info.params)
+    }
+
+    ///
+    /// Constructs the strategy described by `info`, or the default one if `info` is `None`.
+    ///
+    pub fn create_or_default(
+        &self,
+        info: Option<&StrategyInfo>,
+    ) -> Result<Box<dyn PlayerStrategy + Send>, String> {
+        match info {
+            Some(info) => self.create(info),
+            None => Ok(ExpensiveHousesProtectionStrategy::new_boxed()),
+        }
+    }
+}
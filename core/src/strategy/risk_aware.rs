@@ -0,0 +1,423 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::board::{Board, JAIL_FEE, JAIL_POSITION};
+use crate::events::EventKind;
+use crate::observer::StrategyAction;
+use crate::places::BoardPlace;
+use crate::player::PlayerState;
+use crate::serialization::StrategyInfo;
+use crate::strategy::expensive_houses_protection::{ExpensiveHousesProtectionParams, Reserve};
+use crate::strategy::{ExpensiveHousesProtectionStrategy, GameView, PlayerStrategy};
+
+///
+/// Holds the parameters of `RiskAwareStrategy`.
+///
+/// `threshold` is the acceptable probability of being unable to pay in the next `turns` turns.
+///
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RiskAwareParams {
+    pub threshold: f64,
+    pub turns: u32,
+}
+
+impl Default for RiskAwareParams {
+    fn default() -> Self {
+        RiskAwareParams {
+            threshold: 0.05,
+            turns: 1,
+        }
+    }
+}
+
+///
+/// Keeps the reserve sized from the probability distribution of the money to pay in the next turns.
+///
+/// It builds and buys only while the probability of ruin stays under the threshold.
+///
+#[derive(Clone)]
+pub struct RiskAwareStrategy {
+    params: RiskAwareParams,
+}
+
+impl RiskAwareStrategy {
+    ///
+    /// The name of the strategy in `StrategyRegistry`.
+    ///
+    pub const NAME: &'static str = "risk_aware";
+
+    pub fn new_boxed(params: RiskAwareParams) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(RiskAwareStrategy { params })
+    }
+
+    ///
+    /// Gets the distribution of the money the player of the view pays in the next turns.
+    ///
+    fn get_distribution(&self, view: &GameView) -> Vec<(i64, f64)> {
+        let player = view.get_me();
+        get_outflow_distribution(
+            view.board,
+            view.player_id,
+            player.position,
+            &player.state,
+            self.params.turns,
+        )
+    }
+}
+
+impl PlayerStrategy for RiskAwareStrategy {
+    fn raise(&self, view: &GameView, debt: u32) -> Vec<StrategyAction> {
+        ExpensiveHousesProtectionStrategy::default().raise(view, debt)
+    }
+
+    fn invest(&self, view: &GameView) -> Vec<StrategyAction> {
+        // Avoids calculating the distribution when there is nothing to do.
+        if view.board.get_buildable_places(view.player_id).is_empty()
+            && view
+                .board
+                .get_unmortgageable_places(view.player_id)
+                .is_empty()
+        {
+            return Vec::new();
+        }
+
+        let distribution = self.get_distribution(view);
+        let reserve = get_reserve(&distribution, self.params.threshold);
+
+        let params = ExpensiveHousesProtectionParams {
+            reserve: Reserve::Fixed(reserve),
+            ..Default::default()
+        };
+        ExpensiveHousesProtectionStrategy::new(params).invest(view)
+    }
+
+    fn buy(&self, view: &GameView, place_id: usize, price: u32) -> Vec<StrategyAction> {
+        let money = view.get_money();
+        if price > money {
+            return Vec::new();
+        }
+
+        let distribution = self.get_distribution(view);
+        if get_ruin_probability(&distribution, money - price) < self.params.threshold {
+            vec![StrategyAction::Buy(place_id)]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn get_info(&self) -> StrategyInfo {
+        StrategyInfo::with_params(Self::NAME, &self.params)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(self.clone())
+    }
+}
+
+///
+/// Where the player is during the estimation.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Location {
+    Free(usize),
+    Jailed(u8),
+}
+
+///
+/// Calculates the exact probability distribution of the money the player pays in the next turns.
+///
+/// Since Chance and Community Chest do nothing, the distribution depends only on the dices.
+/// The money gained by passing GO is regarded as negative payment.
+/// Returns pairs of the amount and its probability in ascending order of the amount.
+///
+pub fn get_outflow_distribution(
+    board: &Board,
+    player_id: usize,
+    position: usize,
+    state: &PlayerState,
+    turns: u32,
+) -> Vec<(i64, f64)> {
+    let location = match *state {
+        PlayerState::InJail(count) => Location::Jailed(count),
+        _ => Location::Free(position),
+    };
+
+    let mut states = HashMap::from([((location, 0_i64), 1.0)]);
+    for _ in 0..turns {
+        let mut next_states = HashMap::new();
+        for ((location, outflow), prob) in states {
+            spend_turn(
+                board,
+                player_id,
+                location,
+                outflow,
+                prob,
+                0,
+                &mut next_states,
+            );
+        }
+        states = next_states;
+    }
+
+    let mut distribution = HashMap::new();
+    for ((_, outflow), prob) in states {
+        *distribution.entry(outflow).or_insert(0.0) += prob;
+    }
+    let mut distribution = distribution.into_iter().collect::<Vec<_>>();
+    distribution.sort_by_key(|(outflow, _)| *outflow);
+    distribution
+}
+
+///
+/// Gets the probability that the payment exceeds the money.
+///
+pub fn get_ruin_probability(distribution: &[(i64, f64)], money: u32) -> f64 {
+    distribution
+        .iter()
+        .filter(|(outflow, _)| *outflow > money as i64)
+        .map(|(_, prob)| prob)
+        .sum()
+}
+
+///
+/// Gets the least money which keeps the probability of ruin under the threshold.
+///
+pub fn get_reserve(distribution: &[(i64, f64)], threshold: f64) -> u32 {
+    let mut tail = get_ruin_probability(distribution, 0);
+    if tail < threshold {
+        return 0;
+    }
+
+    let mut reserve = 0;
+    for &(outflow, prob) in distribution.iter().filter(|(outflow, _)| *outflow > 0) {
+        tail -= prob;
+        reserve = outflow as u32;
+        if tail < threshold {
+            break;
+        }
+    }
+    reserve
+}
+
+///
+/// Adds the outcomes of a turn from the location to `next_states`.
+///
+/// `doubles` is the number of doubles rolled in a row so far.
+///
+fn spend_turn(
+    board: &Board,
+    player_id: usize,
+    location: Location,
+    outflow: i64,
+    prob: f64,
+    doubles: u32,
+    next_states: &mut HashMap<(Location, i64), f64>,
+) {
+    for first in 1..=6 {
+        for second in 1..=6 {
+            let prob = prob / 36.0;
+            let steps = first + second;
+            let is_double = first == second;
+
+            let outcomes = match location {
+                Location::Jailed(count) => {
+                    if is_double {
+                        land(board, player_id, JAIL_POSITION, steps, outflow, prob)
+                    } else if count == 2 {
+                        let outflow = outflow + JAIL_FEE as i64;
+                        land(board, player_id, JAIL_POSITION, steps, outflow, prob)
+                    } else {
+                        vec![(Location::Jailed(count + 1), outflow, prob)]
+                    }
+                }
+                Location::Free(position) if is_double && doubles == 2 => {
+                    // The third double still moves the player, who is jailed after landing.
+                    land(board, player_id, position, steps, outflow, prob)
+                        .into_iter()
+                        .map(|(_, outflow, prob)| (Location::Jailed(0), outflow, prob))
+                        .collect()
+                }
+                Location::Free(position) => land(board, player_id, position, steps, outflow, prob),
+            };
+
+            for (next_location, outflow, prob) in outcomes {
+                match (location, next_location) {
+                    (Location::Free(_), Location::Free(_)) if is_double => spend_turn(
+                        board,
+                        player_id,
+                        next_location,
+                        outflow,
+                        prob,
+                        doubles + 1,
+                        next_states,
+                    ),
+                    _ => {
+                        *next_states.entry((next_location, outflow)).or_insert(0.0) += prob;
+                    }
+                }
+            }
+        }
+    }
+}
+
+///
+/// Lists up the outcomes of moving from the position.
+///
+fn land(
+    board: &Board,
+    player_id: usize,
+    position: usize,
+    steps: usize,
+    mut outflow: i64,
+    prob: f64,
+) -> Vec<(Location, i64, f64)> {
+    let mut new_position = position + steps;
+    if new_position >= board.places.len() {
+        new_position -= board.places.len();
+
+        // The player receives $200 when passing GO.
+        outflow -= 200;
+    }
+
+    let location = Location::Free(new_position);
+    match board.places[new_position].get_action(player_id, board) {
+        EventKind::PayToOther(_, _, dollars) | EventKind::PayToBank(_, dollars) => {
+            vec![(location, outflow + dollars as i64, prob)]
+        }
+        EventKind::PayToOtherByDice(_, _, multiplier) => {
+            let mut outcomes = Vec::new();
+            for first in 1..=6 {
+                for second in 1..=6 {
+                    let dollars = (first + second) * multiplier;
+                    outcomes.push((location, outflow + dollars as i64, prob / 36.0));
+                }
+            }
+            outcomes
+        }
+        EventKind::Reward(_, dollars) => vec![(location, outflow - dollars as i64, prob)],
+        EventKind::GetJailed => vec![(Location::Jailed(0), outflow, prob)],
+        _ => vec![(location, outflow, prob)],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::board::{GameSession, LogLevel, JAIL_POSITION};
+    use crate::places::{BoardColor, BoardPlace};
+    use crate::player::PlayerState;
+    use crate::strategy::never_buy_above::NeverBuyAboveParams;
+    use crate::strategy::risk_aware::get_outflow_distribution;
+    use crate::strategy::NeverBuyAboveStrategy;
+
+    ///
+    /// Generates a game where PLAYER1 owns the estates and the railroads, and PLAYER0 never buys or builds.
+    ///
+    fn get_game() -> GameSession {
+        let mut game = GameSession::new(2);
+        game.set_log_level(LogLevel::Off);
+        for place_id in 0..game.board.places.len() {
+            // The utilities are left unowned, since their rents roll the dices again.
+            let place = &game.board.places[place_id];
+            if place.is_property() && place.get_color() != BoardColor::Utilities {
+                game.board.set_owner(place_id, Some(1));
+            }
+        }
+        let player = game.get_player_mut(0);
+        player.money = 100000;
+        player.set_strategy(NeverBuyAboveStrategy::new_boxed(NeverBuyAboveParams {
+            limit: 0,
+        }));
+        game
+    }
+
+    ///
+    /// Plays the turn of PLAYER0 for every sequence of the dices, and sums up the probabilities of the payments.
+    ///
+    /// The dices are rolled again only after doubles, up to three times.
+    ///
+    fn play_every_roll(
+        game: &GameSession,
+        rolls: &mut Vec<(u32, u32)>,
+        prob: f64,
+        distribution: &mut HashMap<i64, f64>,
+    ) {
+        for first in 1..=6 {
+            for second in 1..=6 {
+                rolls.push((first, second));
+                let prob = prob / 36.0;
+                let jailed = matches!(game.get_player(0).state, PlayerState::InJail(_));
+                if first == second && rolls.len() < 3 && !jailed {
+                    play_every_roll(game, rolls, prob, distribution);
+                } else {
+                    let mut played = game.clone();
+                    played.script_dices(rolls.clone());
+                    played.spend_one_turn();
+                    let outflow =
+                        game.get_player(0).money as i64 - played.get_player(0).money as i64;
+                    *distribution.entry(outflow).or_insert(0.0) += prob;
+                }
+                rolls.pop();
+            }
+        }
+    }
+
+    ///
+    /// Checks the calculated distribution against the turn played by the game.
+    ///
+    fn assert_distribution(game: &GameSession) {
+        let player = game.get_player(0);
+        let calculated =
+            get_outflow_distribution(&game.board, 0, player.position, &player.state, 1);
+
+        let mut played = HashMap::new();
+        play_every_roll(game, &mut Vec::new(), 1.0, &mut played);
+        let mut played = played.into_iter().collect::<Vec<_>>();
+        played.sort_by_key(|(outflow, _)| *outflow);
+
+        assert_eq!(
+            calculated
+                .iter()
+                .map(|(outflow, _)| *outflow)
+                .collect::<Vec<_>>(),
+            played
+                .iter()
+                .map(|(outflow, _)| *outflow)
+                .collect::<Vec<_>>()
+        );
+        for ((outflow, calculated), (_, played)) in calculated.iter().zip(played.iter()) {
+            assert!(
+                (calculated - played).abs() < 1e-12,
+                "${}: {} != {}",
+                outflow,
+                calculated,
+                played
+            );
+        }
+    }
+
+    #[test]
+    fn distribution_matches_game_from_go() {
+        assert_distribution(&get_game());
+    }
+
+    #[test]
+    fn distribution_matches_game_before_go() {
+        // The doubles can pass GO, and the third one can land on the estates after it.
+        let mut game = get_game();
+        game.get_player_mut(0).position = 34;
+        assert_distribution(&game);
+    }
+
+    #[test]
+    fn distribution_matches_game_in_jail() {
+        let mut game = get_game();
+        let player = game.get_player_mut(0);
+        player.position = JAIL_POSITION;
+        player.state = PlayerState::InJail(2);
+        assert_distribution(&game);
+    }
+}
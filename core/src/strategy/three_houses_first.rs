@@ -0,0 +1,76 @@
+use crate::observer::StrategyAction;
+use crate::places::BoardPlace;
+use crate::serialization::StrategyInfo;
+use crate::strategy::{
+    build_within, get_monopolized_colors, unmortgage_within, ExpensiveHousesProtectionStrategy,
+    GameView, Plan, PlayerStrategy,
+};
+
+///
+/// Builds three houses on every monopolized color before building more.
+///
+/// The third house usually raises the rent the most for its cost.
+///
+#[derive(Clone)]
+pub struct ThreeHousesFirstStrategy;
+
+impl ThreeHousesFirstStrategy {
+    ///
+    /// The name of the strategy in `StrategyRegistry`.
+    ///
+    pub const NAME: &'static str = "three_houses_first";
+
+    pub fn new_boxed() -> Box<dyn PlayerStrategy + Send> {
+        Box::new(ThreeHousesFirstStrategy {})
+    }
+
+    ///
+    /// Unmortgages places and builds houses with the money beyond the reserve.
+    ///
+    fn invest_on(&self, plan: &mut Plan) {
+        let board = plan.board;
+        let player_id = plan.player_id;
+        let usable = plan.get_money() as i32 - board.get_most_expensive(player_id) as i32;
+        if usable <= 0 {
+            return;
+        }
+        let mut usable = usable as u32;
+
+        let colors = get_monopolized_colors(board, player_id);
+        for color in &colors {
+            if !unmortgage_within(plan, &mut usable, |place| place.get_color() == *color) {
+                return;
+            }
+        }
+
+        for max_houses in [3, 5] {
+            for color in &colors {
+                if !build_within(plan, color, max_houses, &mut usable) {
+                    return;
+                }
+            }
+        }
+
+        unmortgage_within(plan, &mut usable, |_| true);
+    }
+}
+
+impl PlayerStrategy for ThreeHousesFirstStrategy {
+    fn raise(&self, view: &GameView, debt: u32) -> Vec<StrategyAction> {
+        ExpensiveHousesProtectionStrategy::default().raise(view, debt)
+    }
+
+    fn invest(&self, view: &GameView) -> Vec<StrategyAction> {
+        let mut plan = Plan::new(view);
+        self.invest_on(&mut plan);
+        plan.into_actions()
+    }
+
+    fn get_info(&self) -> StrategyInfo {
+        StrategyInfo::new(Self::NAME)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(self.clone())
+    }
+}
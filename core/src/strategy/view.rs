@@ -0,0 +1,202 @@
+use crate::action::Decision;
+use crate::board::{Board, GameSession, LogLevel};
+use crate::observer::StrategyAction;
+use crate::places::BoardPlace;
+use crate::player::{Player, PlayerState};
+use crate::serialization::GameInfo;
+
+///
+/// A read-only view of the game given to a strategy.
+///
+/// `player_id` is the id of the player whom the strategy decides for.
+///
+pub struct GameView<'a> {
+    pub player_id: usize,
+    pub turn: usize,
+    pub turn_count: usize,
+    pub board: &'a Board,
+    pub players: &'a [Player],
+}
+
+impl GameView<'_> {
+    ///
+    /// Gets the player whom the strategy decides for.
+    ///
+    pub fn get_me(&self) -> &Player {
+        &self.players[self.player_id]
+    }
+
+    ///
+    /// Gets the money of the player whom the strategy decides for.
+    ///
+    pub fn get_money(&self) -> u32 {
+        self.get_me().money
+    }
+
+    ///
+    /// Gets the other players who are still in the game.
+    ///
+    pub fn get_opponents(&self) -> impl Iterator<Item = &Player> {
+        self.players
+            .iter()
+            .filter(|player| player.player_id != self.player_id && !player.is_bankrupted())
+    }
+
+    ///
+    /// Converts the view into `GameInfo`.
+    ///
+    pub fn to_info(&self) -> GameInfo {
+        GameInfo {
+            turn: self.turn,
+            players: self
+                .players
+                .iter()
+                .map(|player| player.get_info())
+                .collect(),
+            places: self.board.get_infos(),
+        }
+    }
+
+    ///
+    /// Converts the view into a game without logs, where every player follows the default strategy.
+    ///
+    pub(crate) fn to_session(&self) -> GameSession {
+        // The strategies are left out, so constructing the game never fails.
+        let mut info = self.to_info();
+        for player in &mut info.players {
+            player.strategy = None;
+        }
+        let mut game = GameSession::from_info(&info).unwrap();
+        game.set_log_level(LogLevel::Off);
+        game
+    }
+
+    ///
+    /// Enumerates the actions which the player can take on the decision.
+    ///
+    /// Trades are not enumerated since they need an agreement with another player.
+    ///
+    pub fn get_legal_actions(&self, decision: &Decision) -> Vec<StrategyAction> {
+        let player = self.get_me();
+        if player.is_bankrupted() {
+            return Vec::new();
+        }
+
+        let mut actions = Vec::new();
+        match *decision {
+            Decision::Buy(place_id, _) => {
+                if self
+                    .board
+                    .places
+                    .get(place_id)
+                    .is_some_and(|place| place.is_property() && place.get_owner().is_none())
+                {
+                    actions.push(StrategyAction::Buy(place_id));
+                }
+            }
+            Decision::LeaveJail => {
+                if let PlayerState::InJail(_) = player.state {
+                    actions.push(StrategyAction::PayJailFee);
+                }
+            }
+            Decision::Raise(_) | Decision::Invest => {}
+        }
+
+        let board = self.board;
+        actions.extend(
+            board
+                .get_buildable_places(self.player_id)
+                .into_iter()
+                .filter(|&id| board.places[id].get_price_of_house().unwrap() <= player.money)
+                .map(StrategyAction::BuildHouse),
+        );
+        actions.extend(
+            board
+                .get_sellable_places(self.player_id)
+                .into_iter()
+                .map(StrategyAction::SellHouse),
+        );
+        actions.extend(
+            board
+                .get_mortgageable_places(self.player_id)
+                .into_iter()
+                .map(StrategyAction::Mortgage),
+        );
+        actions.extend(
+            board
+                .get_unmortgageable_places(self.player_id)
+                .into_iter()
+                .filter(|&id| board.places[id].get_return_cost() <= player.money)
+                .map(StrategyAction::Unmortgage),
+        );
+        actions
+    }
+
+    ///
+    /// Checks that the actions can be taken in order on the decision, applying each of them to a copy of the game.
+    ///
+    /// This accepts the sequences which `get_legal_actions` cannot tell, such as selling houses and then mortgaging the place.
+    /// Trades are not checked since they need an agreement with another player.
+    ///
+    pub fn validate_actions(
+        &self,
+        decision: &Decision,
+        actions: &[StrategyAction],
+    ) -> Result<(), String> {
+        // The offered place or the jail fee is taken after the other actions, like the game does.
+        let mut expected = match *decision {
+            Decision::Buy(place_id, _) => Some(StrategyAction::Buy(place_id)),
+            Decision::LeaveJail => Some(StrategyAction::PayJailFee),
+            Decision::Raise(_) | Decision::Invest => None,
+        }
+        .filter(|action| self.get_legal_actions(decision).contains(action));
+
+        // Taking only the offered place or the jail fee needs no copy of the game.
+        let mut taken = actions
+            .iter()
+            .filter(|action| !matches!(action, StrategyAction::Trade(_)));
+        match (taken.next(), taken.next()) {
+            (None, _) => return Ok(()),
+            (Some(action), None) if expected.as_ref() == Some(action) => return Ok(()),
+            _ => {}
+        }
+
+        let mut game = self.to_session();
+        for action in actions {
+            if matches!(action, StrategyAction::Trade(_)) {
+                continue;
+            }
+            if expected.as_ref() == Some(action) {
+                expected = None;
+                continue;
+            }
+            game.apply_action(self.player_id, action.clone())
+                .map_err(|error| {
+                    format!("An illegal action {:?} is taken. {}", action, error.reason)
+                })?;
+        }
+        Ok(())
+    }
+}
+
+impl GameSession {
+    ///
+    /// Gets a read-only view of the game for the player.
+    ///
+    pub fn get_view(&self, player_id: usize) -> GameView<'_> {
+        GameView {
+            player_id,
+            turn: self.turn,
+            turn_count: self.turn_count,
+            board: &self.board,
+            players: &self.players,
+        }
+    }
+
+    ///
+    /// Enumerates the actions which the player can take on the decision.
+    ///
+    pub fn get_legal_actions(&self, player_id: usize, decision: &Decision) -> Vec<StrategyAction> {
+        self.get_view(player_id).get_legal_actions(decision)
+    }
+}
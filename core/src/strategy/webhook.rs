@@ -0,0 +1,143 @@
+use std::cell::RefCell;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use ureq::{Agent, AgentBuilder};
+
+use crate::action::Decision;
+use crate::observer::{StrategyAction, TradeOffer};
+use crate::serialization::StrategyInfo;
+use crate::strategy::external::{get_request, BotResponse};
+use crate::strategy::{decide, ExpensiveHousesProtectionStrategy, GameView, PlayerStrategy};
+
+///
+/// Holds the parameters of `WebhookStrategy`.
+///
+/// `timeout` is how long the service can take for a decision in milliseconds.
+///
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookParams {
+    pub url: String,
+    pub timeout: u64,
+    pub fallback: StrategyInfo,
+}
+
+impl Default for WebhookParams {
+    fn default() -> Self {
+        WebhookParams {
+            url: String::new(),
+            timeout: 1000,
+            fallback: StrategyInfo::new(ExpensiveHousesProtectionStrategy::NAME),
+        }
+    }
+}
+
+///
+/// Asks a service over HTTP for the decisions.
+///
+/// Every decision is POSTed to the URL as JSON `{"player_id", "decision", "game"}`,
+/// and the service responds with JSON `{"actions"}`, in the same way as `ExternalStrategy`.
+/// If the service fails to respond in time or responds actions which are not legal,
+/// the fallback strategy decides instead for the decision, and the reason is reported to the session log.
+///
+#[derive(Clone)]
+pub struct WebhookStrategy {
+    params: WebhookParams,
+    fallback: Box<dyn PlayerStrategy + Send>,
+    agent: Agent,
+    reports: RefCell<Vec<String>>,
+}
+
+impl WebhookStrategy {
+    ///
+    /// The name of the strategy in `StrategyRegistry`.
+    ///
+    pub const NAME: &'static str = "webhook";
+
+    pub fn new_boxed(
+        params: WebhookParams,
+        fallback: Box<dyn PlayerStrategy + Send>,
+    ) -> Box<dyn PlayerStrategy + Send> {
+        let agent = AgentBuilder::new()
+            .timeout(Duration::from_millis(params.timeout))
+            .build();
+        Box::new(WebhookStrategy {
+            params,
+            fallback,
+            agent,
+            reports: RefCell::new(Vec::new()),
+        })
+    }
+
+    ///
+    /// Asks the service for the decision, or the fallback strategy if the service fails.
+    ///
+    fn decide(&self, view: &GameView, decision: Decision) -> Vec<StrategyAction> {
+        match self.ask(view, &decision) {
+            Ok(actions) => actions,
+            Err(msg) => {
+                self.reports.borrow_mut().push(format!(
+                    "{} Falls back to \"{}\".",
+                    msg, self.params.fallback.name
+                ));
+                decide(self.fallback.as_ref(), view, &decision)
+            }
+        }
+    }
+
+    ///
+    /// Sends the decision to the service and validates its response.
+    ///
+    fn ask(&self, view: &GameView, decision: &Decision) -> Result<Vec<StrategyAction>, String> {
+        let response: BotResponse = self
+            .agent
+            .post(&self.params.url)
+            .send_json(get_request(view, decision))
+            .map_err(|err| format!("The service does not respond. {}", err))?
+            .into_json()
+            .map_err(|err| format!("The service responds invalid JSON. {}", err))?;
+
+        view.validate_actions(decision, &response.actions)?;
+
+        Ok(response.actions)
+    }
+}
+
+impl PlayerStrategy for WebhookStrategy {
+    fn raise(&self, view: &GameView, debt: u32) -> Vec<StrategyAction> {
+        self.decide(view, Decision::Raise(debt))
+    }
+
+    fn invest(&self, view: &GameView) -> Vec<StrategyAction> {
+        self.decide(view, Decision::Invest)
+    }
+
+    fn buy(&self, view: &GameView, place_id: usize, price: u32) -> Vec<StrategyAction> {
+        self.decide(view, Decision::Buy(place_id, price))
+    }
+
+    fn leave_jail(&self, view: &GameView) -> Vec<StrategyAction> {
+        self.decide(view, Decision::LeaveJail)
+    }
+
+    fn accepts_trade(&self, view: &GameView, proposer: usize, offer: &TradeOffer) -> bool {
+        self.fallback.accepts_trade(view, proposer, offer)
+    }
+
+    fn get_info(&self) -> StrategyInfo {
+        StrategyInfo::with_params(Self::NAME, &self.params)
+    }
+
+    fn take_reports(&self) -> Vec<String> {
+        self.reports.take()
+    }
+
+    fn set_seed(&self, seed: u64) {
+        self.fallback.set_seed(seed);
+    }
+
+    fn clone_boxed(&self) -> Box<dyn PlayerStrategy + Send> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,292 @@
+use std::cmp::Reverse;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::appraiser::Appraiser;
+use crate::board::{GameSession, LogLevel};
+use crate::serialization::StrategyInfo;
+use crate::simulation::get_strategy_seed;
+use crate::statistics::get_wilson_interval;
+use crate::strategy::StrategyRegistry;
+
+///
+/// The rating which every entrant starts with.
+///
+const INITIAL_RATING: f64 = 1500.0;
+
+///
+/// The factor of rating updates between a pair of entrants in a two-player game.
+///
+const RATING_FACTOR: f64 = 32.0;
+
+///
+/// The maximum number of the seat orders played in a tournament.
+///
+/// If the strategies have more permutations than this, the seat orders are sampled at random.
+///
+pub const MAX_SEATINGS: usize = 120;
+
+///
+/// Holds the parameters of `Tournament`.
+///
+/// Each of `strategies` takes a seat, and `games` games are played for every permutation of the seats,
+/// or for `MAX_SEATINGS` random ones if there are more permutations.
+/// A game ends when only one player survives or `max_turns` turns are played.
+/// If `seed` is `None`, a random seed is used.
+///
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TournamentParams {
+    pub strategies: Vec<StrategyInfo>,
+    pub games: u32,
+    pub max_turns: usize,
+    pub seed: Option<u64>,
+}
+
+impl Default for TournamentParams {
+    fn default() -> Self {
+        TournamentParams {
+            strategies: Vec::new(),
+            games: 10,
+            max_turns: 1000,
+            seed: None,
+        }
+    }
+}
+
+///
+/// Holds the result of an entrant of a tournament.
+///
+/// Positions start from 1, which means the winner.
+/// The intervals are 95% confidence intervals; the Wilson score interval is used for the win rate.
+///
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EntrantResult {
+    pub strategy: StrategyInfo,
+    pub wins: u32,
+    pub win_rate: f64,
+    pub win_rate_interval: (f64, f64),
+    pub average_position: f64,
+    pub position_interval: (f64, f64),
+    pub rating: f64,
+}
+
+///
+/// Holds a result of a tournament.
+///
+/// `entrants` are in the same order as the strategies of the parameters.
+///
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TournamentResult {
+    pub games: u32,
+    pub average_length: f64,
+    pub entrants: Vec<EntrantResult>,
+}
+
+///
+/// Plays games among strategies in every seat order, or in sampled ones, to cancel the advantage of moving first.
+///
+pub struct Tournament {
+    params: TournamentParams,
+}
+
+impl Tournament {
+    ///
+    /// Generates a tournament.
+    ///
+    pub fn new(params: TournamentParams) -> Self {
+        Tournament { params }
+    }
+
+    ///
+    /// Runs the tournament.
+    ///
+    /// Fails if there are fewer than two strategies, no games, or a strategy which cannot be constructed.
+    ///
+    pub fn run(&self) -> Result<TournamentResult, String> {
+        let entrant_num = self.params.strategies.len();
+        if entrant_num < 2 {
+            return Err("A tournament needs two strategies at least.".to_string());
+        }
+        if self.params.games == 0 {
+            return Err("A tournament needs a game at least.".to_string());
+        }
+        for info in &self.params.strategies {
+            StrategyRegistry::get_installed().create(info)?;
+        }
+
+        let mut rng = StdRng::seed_from_u64(self.params.seed.unwrap_or_else(rand::random));
+
+        let mut games = 0;
+        let mut total_length = 0;
+        let mut wins = vec![0_u32; entrant_num];
+        let mut position_sums = vec![0.0_f64; entrant_num];
+        let mut position_square_sums = vec![0.0_f64; entrant_num];
+        let mut ratings = vec![INITIAL_RATING; entrant_num];
+
+        for seats in get_seatings(entrant_num, &mut rng) {
+            for _ in 0..self.params.games {
+                let (positions, length) = self.play(&seats, rng.gen());
+
+                // Indexes the positions by entrants instead of seats.
+                let mut entrant_positions = vec![0; entrant_num];
+                for (seat, &entrant) in seats.iter().enumerate() {
+                    entrant_positions[entrant] = positions[seat];
+                }
+
+                games += 1;
+                total_length += length;
+                for (entrant, &position) in entrant_positions.iter().enumerate() {
+                    if position == 1 {
+                        wins[entrant] += 1;
+                    }
+                    position_sums[entrant] += position as f64;
+                    position_square_sums[entrant] += (position * position) as f64;
+                }
+                update_ratings(&mut ratings, &entrant_positions);
+            }
+        }
+
+        let entrants = (0..entrant_num)
+            .map(|entrant| {
+                let n = games as f64;
+                let win_rate = wins[entrant] as f64 / n;
+                let average_position = position_sums[entrant] / n;
+                let variance = (position_square_sums[entrant] / n
+                    - average_position * average_position)
+                    .max(0.0);
+                let half = 1.96 * (variance / n).sqrt();
+
+                EntrantResult {
+                    strategy: self.params.strategies[entrant].clone(),
+                    wins: wins[entrant],
+                    win_rate,
+                    win_rate_interval: get_wilson_interval(win_rate, games),
+                    average_position,
+                    position_interval: (average_position - half, average_position + half),
+                    rating: ratings[entrant],
+                }
+            })
+            .collect();
+
+        Ok(TournamentResult {
+            games,
+            average_length: total_length as f64 / games as f64,
+            entrants,
+        })
+    }
+
+    ///
+    /// Plays a game where `seats[i]` is the entrant taking the seat `i`.
+    ///
+    /// Returns the positions of the seats and the number of turns played.
+    ///
+    fn play(&self, seats: &[usize], seed: u64) -> (Vec<usize>, usize) {
+        let mut game = GameSession::new(seats.len() as u32);
+        game.set_seed(seed);
+        game.set_log_level(LogLevel::Off);
+        for (seat, &entrant) in seats.iter().enumerate() {
+            let strategy = StrategyRegistry::get_installed()
+                .create(&self.params.strategies[entrant])
+                .unwrap();
+            // Reseeds the strategy as well, so that the game is reproduced from the seed.
+            strategy.set_seed(get_strategy_seed(seed, seat));
+            game.get_player_mut(seat).set_strategy(strategy);
+        }
+
+        let mut bankrupted_at = vec![None; seats.len()];
+        let mut length = 0;
+        while length < self.params.max_turns && game.count_active_players() > 1 {
+            game.spend_one_turn();
+            length += 1;
+
+            for (seat, turn) in bankrupted_at.iter_mut().enumerate() {
+                if turn.is_none() && game.get_player(seat).is_bankrupted() {
+                    *turn = Some(length);
+                }
+            }
+        }
+
+        // The survivors are ranked by their payable money, and the others by how long they survived.
+        let mut ranking = (0..seats.len()).collect::<Vec<_>>();
+        ranking.sort_by_key(|&seat| match bankrupted_at[seat] {
+            None => {
+                let money = Appraiser::get_payable_money(game.get_player(seat), &game.board);
+                (false, Reverse(money as usize))
+            }
+            Some(turn) => (true, Reverse(turn)),
+        });
+
+        let mut positions = vec![0; seats.len()];
+        for (rank, &seat) in ranking.iter().enumerate() {
+            positions[seat] = rank + 1;
+        }
+        (positions, length)
+    }
+}
+
+///
+/// Gets the seat orders played, every permutation of `0..n` or `MAX_SEATINGS` random ones if there are more.
+///
+fn get_seatings(n: usize, rng: &mut StdRng) -> Vec<Vec<usize>> {
+    let mut permutation_num = 1_usize;
+    for i in 2..=n {
+        permutation_num = permutation_num.saturating_mul(i);
+    }
+    if permutation_num <= MAX_SEATINGS {
+        return get_permutations(n);
+    }
+
+    (0..MAX_SEATINGS)
+        .map(|_| {
+            let mut seats = (0..n).collect::<Vec<_>>();
+            seats.shuffle(rng);
+            seats
+        })
+        .collect()
+}
+
+///
+/// Enumerates the permutations of `0..n` in the lexicographic order.
+///
+fn get_permutations(n: usize) -> Vec<Vec<usize>> {
+    let mut permutations = vec![Vec::new()];
+    for _ in 0..n {
+        let mut extended = Vec::new();
+        for permutation in &permutations {
+            for i in (0..n).filter(|i| !permutation.contains(i)) {
+                let mut permutation = permutation.clone();
+                permutation.push(i);
+                extended.push(permutation);
+            }
+        }
+        permutations = extended;
+    }
+    permutations
+}
+
+///
+/// Updates the Elo ratings, regarding a game as matches between every pair of the entrants.
+///
+fn update_ratings(ratings: &mut [f64], positions: &[usize]) {
+    let factor = RATING_FACTOR / (ratings.len() - 1) as f64;
+    let mut deltas = vec![0.0; ratings.len()];
+    for i in 0..ratings.len() {
+        for j in (i + 1)..ratings.len() {
+            let expected = 1.0 / (1.0 + 10_f64.powf((ratings[j] - ratings[i]) / 400.0));
+            let actual = if positions[i] < positions[j] {
+                1.0
+            } else {
+                0.0
+            };
+            deltas[i] += factor * (actual - expected);
+            deltas[j] -= factor * (actual - expected);
+        }
+    }
+    for (rating, delta) in ratings.iter_mut().zip(deltas) {
+        *rating += delta;
+    }
+}
@@ -0,0 +1,349 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::places::BoardColor;
+use crate::serialization::StrategyInfo;
+use crate::strategy::expensive_houses_protection::{
+    ExpensiveHousesProtectionParams, Reserve, UnmortgagePolicy,
+};
+use crate::strategy::ExpensiveHousesProtectionStrategy;
+use crate::tournament::{Tournament, TournamentParams};
+
+///
+/// Holds the parameters of `Tuner`.
+///
+/// Every generation, each of `population` candidates plays a tournament against `opponents`,
+/// and the best `survivors` of them are kept and bred to fill the next generation.
+/// `mutation_rate` is the probability that each parameter of a child mutates.
+/// If `seed` is `None`, a random seed is used.
+///
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TuningParams {
+    pub population: usize,
+    pub survivors: usize,
+    pub mutation_rate: f64,
+    pub opponents: Vec<StrategyInfo>,
+    pub games: u32,
+    pub max_turns: usize,
+    pub seed: Option<u64>,
+}
+
+impl TuningParams {
+    ///
+    /// Checks that the tuning can breed the generations with the parameters.
+    ///
+    pub fn validate(&self) -> Result<(), String> {
+        if self.survivors == 0 {
+            return Err("\"survivors\" must be 1 or more.".to_string());
+        }
+        if self.survivors > self.population {
+            return Err("\"survivors\" must be \"population\" or less.".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.mutation_rate) {
+            return Err("\"mutation_rate\" must be between 0 and 1.".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl Default for TuningParams {
+    fn default() -> Self {
+        TuningParams {
+            population: 16,
+            survivors: 4,
+            mutation_rate: 0.3,
+            opponents: vec![StrategyInfo::new(ExpensiveHousesProtectionStrategy::NAME)],
+            games: 10,
+            max_turns: 1000,
+            seed: None,
+        }
+    }
+}
+
+///
+/// A set of parameters with its win rate in the tournament.
+///
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Candidate {
+    pub params: ExpensiveHousesProtectionParams,
+    pub fitness: f64,
+}
+
+///
+/// Holds the whole state of tuning, from which it can be resumed.
+///
+/// `population` is the candidates of the next generation, which are not evaluated yet.
+/// `best` is the fittest candidate of all the generations so far, evaluated on the dices of the last one, and `history` has the best and the mean fitness of every generation.
+///
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TuningCheckpoint {
+    pub params: TuningParams,
+    pub seed: u64,
+    pub generation: u32,
+    pub population: Vec<ExpensiveHousesProtectionParams>,
+    pub best: Option<Candidate>,
+    pub history: Vec<(f64, f64)>,
+}
+
+impl TuningCheckpoint {
+    ///
+    /// Reconstructs a checkpoint from JSON.
+    ///
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|err| err.to_string())
+    }
+
+    ///
+    /// Parses the checkpoint into a text in JSON.
+    ///
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+///
+/// Optimizes the parameters of `ExpensiveHousesProtectionStrategy` with a genetic algorithm.
+///
+pub struct Tuner {
+    checkpoint: TuningCheckpoint,
+}
+
+impl Tuner {
+    ///
+    /// Generates a tuner starting from the default parameters and their mutations.
+    ///
+    /// Fails if the parameters are invalid, e.g. when no candidate survives.
+    ///
+    pub fn new(params: TuningParams) -> Result<Self, String> {
+        params.validate()?;
+
+        let seed = params.seed.unwrap_or_else(rand::random);
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let default = ExpensiveHousesProtectionParams::default();
+        let mut population = vec![default.clone()];
+        while population.len() < params.population {
+            population.push(mutate(&default, 1.0, &mut rng));
+        }
+
+        Ok(Tuner {
+            checkpoint: TuningCheckpoint {
+                params,
+                seed,
+                generation: 0,
+                population,
+                best: None,
+                history: Vec::new(),
+            },
+        })
+    }
+
+    ///
+    /// Resumes tuning from the checkpoint.
+    ///
+    /// Fails if the parameters are invalid or there is no candidate to evaluate.
+    ///
+    pub fn from_checkpoint(checkpoint: TuningCheckpoint) -> Result<Self, String> {
+        checkpoint.params.validate()?;
+        if checkpoint.population.is_empty() {
+            return Err("The checkpoint has no candidate.".to_string());
+        }
+        Ok(Tuner { checkpoint })
+    }
+
+    ///
+    /// Gets the current state of tuning.
+    ///
+    pub fn get_checkpoint(&self) -> &TuningCheckpoint {
+        &self.checkpoint
+    }
+
+    ///
+    /// Gets the strategy with the best parameters so far, from which the strategy can be constructed.
+    ///
+    pub fn get_best_info(&self) -> Option<StrategyInfo> {
+        self.checkpoint.best.as_ref().map(|best| {
+            StrategyInfo::with_params(ExpensiveHousesProtectionStrategy::NAME, &best.params)
+        })
+    }
+
+    ///
+    /// Evaluates the current generation and breeds the next one.
+    ///
+    /// Fails if the tournaments cannot be held, e.g. when one of the opponents is not registered.
+    ///
+    pub fn step(&mut self) -> Result<(), String> {
+        let checkpoint = &mut self.checkpoint;
+        let params = &checkpoint.params;
+
+        // Derives the random numbers from the generation so that resumed tuning goes the same way.
+        let mut rng = StdRng::seed_from_u64(
+            checkpoint
+                .seed
+                .wrapping_add(checkpoint.generation as u64 + 1),
+        );
+
+        // Every candidate faces the same dices to compare them fairly.
+        let tournament_seed = rng.gen();
+        let mut candidates = Vec::new();
+        for candidate in &checkpoint.population {
+            candidates.push(Candidate {
+                params: candidate.clone(),
+                fitness: evaluate(params, candidate, tournament_seed)?,
+            });
+        }
+        candidates.sort_by(|a, b| b.fitness.total_cmp(&a.fitness));
+
+        let mean = candidates
+            .iter()
+            .map(|candidate| candidate.fitness)
+            .sum::<f64>()
+            / candidates.len() as f64;
+        checkpoint.history.push((candidates[0].fitness, mean));
+
+        // The survivors are kept as they are, and the rest are their children.
+        let survivors = &candidates[..params.survivors.min(candidates.len())];
+        let mut population = survivors
+            .iter()
+            .map(|candidate| candidate.params.clone())
+            .collect::<Vec<_>>();
+        while population.len() < params.population {
+            let first = &survivors.choose(&mut rng).unwrap().params;
+            let second = &survivors.choose(&mut rng).unwrap().params;
+            let child = crossover(first, second, &mut rng);
+            population.push(mutate(&child, params.mutation_rate, &mut rng));
+        }
+
+        // Keeps the best one so far unless the best of this generation beats it.
+        // The best one so far is evaluated again on the same dices, since the fitness depends on them.
+        let generation_best = candidates.swap_remove(0);
+        let incumbent = match &checkpoint.best {
+            Some(best) => Some(Candidate {
+                params: best.params.clone(),
+                fitness: evaluate(params, &best.params, tournament_seed)?,
+            }),
+            None => None,
+        };
+        checkpoint.best = match incumbent {
+            Some(best) if best.fitness >= generation_best.fitness => Some(best),
+            _ => Some(generation_best),
+        };
+        checkpoint.population = population;
+        checkpoint.generation += 1;
+        Ok(())
+    }
+}
+
+///
+/// Gets the win rate of the candidate in the tournament against the opponents with the seed.
+///
+fn evaluate(
+    params: &TuningParams,
+    candidate: &ExpensiveHousesProtectionParams,
+    seed: u64,
+) -> Result<f64, String> {
+    let mut strategies = vec![StrategyInfo::with_params(
+        ExpensiveHousesProtectionStrategy::NAME,
+        candidate,
+    )];
+    strategies.extend(params.opponents.iter().cloned());
+
+    let result = Tournament::new(TournamentParams {
+        strategies,
+        games: params.games,
+        max_turns: params.max_turns,
+        seed: Some(seed),
+    })
+    .run()?;
+    Ok(result.entrants[0].win_rate)
+}
+
+///
+/// Breeds a child taking each parameter from either of the parents.
+///
+fn crossover(
+    first: &ExpensiveHousesProtectionParams,
+    second: &ExpensiveHousesProtectionParams,
+    rng: &mut StdRng,
+) -> ExpensiveHousesProtectionParams {
+    let mut pick = || if rng.gen_bool(0.5) { first } else { second };
+    ExpensiveHousesProtectionParams {
+        reserve: pick().reserve.clone(),
+        color_priority: pick().color_priority.clone(),
+        unmortgage: pick().unmortgage.clone(),
+        max_houses: pick().max_houses,
+    }
+}
+
+///
+/// Mutates each parameter with the probability of `rate`.
+///
+fn mutate(
+    params: &ExpensiveHousesProtectionParams,
+    rate: f64,
+    rng: &mut StdRng,
+) -> ExpensiveHousesProtectionParams {
+    let mut params = params.clone();
+
+    if rng.gen_bool(rate) {
+        params.reserve = match params.reserve {
+            // Occasionally switches to the other kind of the reserve.
+            _ if rng.gen_bool(0.1) => {
+                if rng.gen_bool(0.5) {
+                    Reserve::MostExpensive(rng.gen_range(0.0..2.0))
+                } else {
+                    Reserve::Fixed(rng.gen_range(0..=1000))
+                }
+            }
+            Reserve::MostExpensive(ratio) => {
+                Reserve::MostExpensive((ratio + rng.gen_range(-0.25..0.25)).clamp(0.0, 3.0))
+            }
+            Reserve::Fixed(money) => {
+                Reserve::Fixed((money as i64 + rng.gen_range(-100..=100)).clamp(0, 2000) as u32)
+            }
+        };
+    }
+
+    if rng.gen_bool(rate) {
+        let priority = &mut params.color_priority;
+        let missing = BoardColor::get_estate_colors()
+            .into_iter()
+            .filter(|color| !priority.contains(color))
+            .collect::<Vec<_>>();
+        match rng.gen_range(0..3) {
+            0 if !missing.is_empty() => {
+                let idx = rng.gen_range(0..=priority.len());
+                priority.insert(idx, missing.choose(rng).unwrap().clone());
+            }
+            1 if !priority.is_empty() => {
+                priority.remove(rng.gen_range(0..priority.len()));
+            }
+            _ if priority.len() >= 2 => {
+                let first = rng.gen_range(0..priority.len());
+                let second = rng.gen_range(0..priority.len());
+                priority.swap(first, second);
+            }
+            _ => {}
+        }
+    }
+
+    if rng.gen_bool(rate) {
+        params.unmortgage = [
+            UnmortgagePolicy::All,
+            UnmortgagePolicy::MonopoliesOnly,
+            UnmortgagePolicy::Never,
+        ]
+        .choose(rng)
+        .unwrap()
+        .clone();
+    }
+
+    if rng.gen_bool(rate) {
+        params.max_houses = rng.gen_range(1..=5);
+    }
+
+    params
+}
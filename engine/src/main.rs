@@ -0,0 +1,233 @@
+use std::io::{stdin, stdout, BufRead, Write};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use mplz_core::action::Decision;
+use mplz_core::advisor::AdvisorParams;
+use mplz_core::appraiser::Appraiser;
+use mplz_core::board::GameSession;
+use mplz_core::command::GameCommand;
+use mplz_core::serialization::GameInfo;
+use mplz_core::simulation::Simulation;
+use mplz_core::strategy::StrategyRegistry;
+
+///
+/// A command which a client sends as a line of JSON.
+///
+#[derive(Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+enum EngineCommand {
+    Init {
+        num: u32,
+    },
+    Load {
+        game: GameInfo,
+    },
+    Get,
+    Step {
+        num: u32,
+    },
+    Tap,
+    Money,
+    Survival {
+        num: u32,
+        depth: usize,
+    },
+    Actions {
+        player_id: usize,
+        decision: Decision,
+    },
+    Advise {
+        player_id: usize,
+        decision: Decision,
+    },
+    SetOption {
+        name: String,
+        value: Value,
+    },
+    Quit,
+}
+
+#[derive(Serialize)]
+struct MoneyBody {
+    money: Vec<u32>,
+    available: Vec<u32>,
+    total: Vec<u32>,
+}
+
+///
+/// Holds the position and the options of the engine.
+///
+#[derive(Default)]
+struct Engine {
+    game: Option<GameSession>,
+    seed: Option<u64>,
+    advisor: AdvisorParams,
+}
+
+impl Engine {
+    ///
+    /// Executes a command, and returns the result to be sent to the client.
+    ///
+    fn execute(&mut self, command: EngineCommand) -> Result<Value, String> {
+        match command {
+            EngineCommand::Init { num } => {
+                GameCommand::Init(num, &mut self.game)
+                    .execute()
+                    .map_err(|err| err.to_string())?;
+                self.apply_options();
+                Ok(Value::Null)
+            }
+            EngineCommand::Load { game } => {
+                self.game = Some(GameSession::from_info(&game)?);
+                self.apply_options();
+                Ok(Value::Null)
+            }
+            EngineCommand::SetOption { name, value } => {
+                match name.as_str() {
+                    "seed" => {
+                        self.seed = serde_json::from_value(value).map_err(|err| err.to_string())?;
+                        self.apply_options();
+                    }
+                    "audit" => {
+                        let enabled =
+                            serde_json::from_value(value).map_err(|err| err.to_string())?;
+                        let game = self.get_game_mut()?;
+                        GameCommand::Audit(enabled, game)
+                            .execute()
+                            .map_err(|err| err.to_string())?;
+                    }
+                    "advisor" => {
+                        self.advisor =
+                            serde_json::from_value(value).map_err(|err| err.to_string())?;
+                    }
+                    _ => return Err(format!("The option \"{}\" does not exist.", name)),
+                }
+                Ok(Value::Null)
+            }
+            EngineCommand::Get => Ok(to_value(&self.get_game()?.to_info())),
+            EngineCommand::Step { num } => {
+                let game = self.get_game_mut()?;
+                GameCommand::Step(num, game)
+                    .execute()
+                    .map_err(|err| err.to_string())?;
+                Ok(to_value(&game.to_info()))
+            }
+            EngineCommand::Tap => {
+                let game = self.get_game()?;
+                let taps = game
+                    .players
+                    .iter()
+                    .map(|player| Appraiser::get_tap(player, &game.board))
+                    .collect::<Vec<_>>();
+                Ok(json!({ "taps": taps }))
+            }
+            EngineCommand::Money => {
+                let game = self.get_game()?;
+                let money = game
+                    .players
+                    .iter()
+                    .map(|player| player.money)
+                    .collect::<Vec<_>>();
+                let total = game
+                    .players
+                    .iter()
+                    .map(|player| Appraiser::get_payable_money(player, &game.board))
+                    .collect::<Vec<_>>();
+                let available = money
+                    .iter()
+                    .zip(total.iter())
+                    .map(|(money, total)| total - money)
+                    .collect();
+                Ok(to_value(&MoneyBody {
+                    money,
+                    available,
+                    total,
+                }))
+            }
+            EngineCommand::Survival { num, depth } => {
+                let simulation = Simulation::from_session(self.get_game()?, num, depth);
+                Ok(to_value(&simulation.get_survival()))
+            }
+            EngineCommand::Actions {
+                player_id,
+                decision,
+            } => {
+                let game = self.get_game()?;
+                check_player(game, player_id)?;
+                let actions = game.get_legal_actions(player_id, &decision);
+                Ok(json!({ "actions": actions }))
+            }
+            EngineCommand::Advise {
+                player_id,
+                decision,
+            } => {
+                let game = self.get_game()?;
+                check_player(game, player_id)?;
+                let ranked = game.get_advice(player_id, &decision, &self.advisor)?;
+                Ok(json!({ "ranked": ranked }))
+            }
+            EngineCommand::Quit => unreachable!(),
+        }
+    }
+
+    ///
+    /// Applies the options to the game.
+    ///
+    fn apply_options(&mut self) {
+        if let (Some(game), Some(seed)) = (&mut self.game, self.seed) {
+            game.set_seed(seed);
+        }
+    }
+
+    fn get_game(&self) -> Result<&GameSession, String> {
+        self.game
+            .as_ref()
+            .ok_or_else(|| "No game is loaded.".to_string())
+    }
+
+    fn get_game_mut(&mut self) -> Result<&mut GameSession, String> {
+        self.game
+            .as_mut()
+            .ok_or_else(|| "No game is loaded.".to_string())
+    }
+}
+
+fn check_player(game: &GameSession, player_id: usize) -> Result<(), String> {
+    if player_id < game.players.len() {
+        Ok(())
+    } else {
+        Err("The player does not exist.".to_string())
+    }
+}
+
+fn to_value<T: Serialize>(value: &T) -> Value {
+    serde_json::to_value(value).unwrap()
+}
+
+fn main() {
+    StrategyRegistry::install(StrategyRegistry::with_external()).unwrap();
+
+    let mut engine = Engine::default();
+    for line in stdin().lock().lines() {
+        let line = line.unwrap();
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let result = match serde_json::from_str(&line) {
+            Ok(EngineCommand::Quit) => break,
+            Ok(command) => engine.execute(command),
+            Err(err) => Err(err.to_string()),
+        };
+        let response = match result {
+            Ok(result) => json!({ "status": "ok", "result": result }),
+            Err(msg) => json!({ "status": "error", "message": msg }),
+        };
+
+        let mut stdout = stdout().lock();
+        writeln!(stdout, "{}", response).unwrap();
+        stdout.flush().unwrap();
+    }
+}
@@ -0,0 +1,183 @@
+#ifndef MPLZ_H
+#define MPLZ_H
+
+/* This file is generated by cbindgen from ffi/src/lib.rs. Do not edit it directly. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/*
+
+ An error code returned by the functions.
+
+ The message of the last error is available through `mplz_get_last_error`.
+
+ */
+typedef enum MplzError {
+  /*
+   Succeeded.
+   */
+  MPLZ_ERROR_OK = 0,
+  /*
+   A pointer is null.
+   */
+  MPLZ_ERROR_NULL_POINTER = 1,
+  /*
+   A string is not valid UTF-8.
+   */
+  MPLZ_ERROR_INVALID_UTF8 = 2,
+  /*
+   A string is not valid JSON of the expected structure.
+   */
+  MPLZ_ERROR_INVALID_JSON = 3,
+  /*
+   An argument is out of range, such as an id of a player who does not exist.
+   */
+  MPLZ_ERROR_INVALID_ARGUMENT = 4,
+  /*
+   The library panicked. The session may be left inconsistent, so it should be freed.
+   */
+  MPLZ_ERROR_PANIC = 5,
+} MplzError;
+
+/*
+
+ A game session. It must be freed with `mplz_session_free`.
+
+ */
+typedef struct MplzSession MplzSession;
+
+/*
+
+ Gets the message of the last error on the thread, or null if no error has occurred.
+
+ The string is owned by the library, and valid until the next error on the thread.
+
+ */
+const char *mplz_get_last_error(void);
+
+/*
+
+ Creates an initialized game with `player_num` players.
+
+ # Safety
+
+ `out` must be a valid pointer to write the session to.
+
+ */
+enum MplzError mplz_session_new(uint32_t player_num, struct MplzSession **out);
+
+/*
+
+ Creates a game from JSON of `IGameInfo`.
+
+ # Safety
+
+ `json` must be a null-terminated string, and `out` must be a valid pointer to write the session to.
+
+ */
+enum MplzError mplz_session_from_json(const char *json, struct MplzSession **out);
+
+/*
+
+ Serializes the game into JSON of `IGameInfo`. The string must be freed with `mplz_string_free`.
+
+ # Safety
+
+ `session` must be a session created by the library, and `out` must be a valid pointer to write the string to.
+
+ */
+enum MplzError mplz_session_to_json(const struct MplzSession *session,
+                                    char **out);
+
+/*
+
+ Simulates `turns` turns.
+
+ # Safety
+
+ `session` must be a session created by the library.
+
+ */
+enum MplzError mplz_session_step(struct MplzSession *session, uint32_t turns);
+
+/*
+
+ Gets the number of the players.
+
+ # Safety
+
+ `session` must be a session created by the library, and `out` must be a valid pointer.
+
+ */
+enum MplzError mplz_session_get_player_num(const struct MplzSession *session, size_t *out);
+
+/*
+
+ Gets TAP of the player.
+
+ # Safety
+
+ `session` must be a session created by the library, and `out` must be a valid pointer.
+
+ */
+enum MplzError mplz_session_get_tap(const struct MplzSession *session,
+                                    size_t player_id,
+                                    uint32_t *out);
+
+/*
+
+ Gets the money which the player can pay, including what they get by selling and mortgaging.
+
+ # Safety
+
+ `session` must be a session created by the library, and `out` must be a valid pointer.
+
+ */
+enum MplzError mplz_session_get_payable_money(const struct MplzSession *session,
+                                              size_t player_id,
+                                              uint32_t *out);
+
+/*
+
+ Simulates `depth` turns `iteration` times, and writes the survival rates of the players to `rates`.
+
+ `len` is the length of `rates`, which must be the number of the players or more.
+
+ # Safety
+
+ `session` must be a session created by the library, and `rates` must be a valid array of `len` elements.
+
+ */
+enum MplzError mplz_session_get_survival(const struct MplzSession *session,
+                                         uint32_t iteration,
+                                         size_t depth,
+                                         float *rates,
+                                         size_t len);
+
+/*
+
+ Frees the session. Does nothing if it is null.
+
+ # Safety
+
+ `session` must be a session created by the library, which has not been freed yet.
+
+ */
+void mplz_session_free(struct MplzSession *session);
+
+/*
+
+ Frees the string returned by the library. Does nothing if it is null.
+
+ # Safety
+
+ `string` must be a string returned by the library, which has not been freed yet.
+
+ */
+void mplz_string_free(char *string);
+
+#endif  /* MPLZ_H */
@@ -0,0 +1,331 @@
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr::null;
+
+use mplz_core::appraiser::Appraiser;
+use mplz_core::board::GameSession;
+use mplz_core::command::GameCommand;
+use mplz_core::serialization::GameInfo;
+use mplz_core::simulation::Simulation;
+
+///
+/// An error code returned by the functions.
+///
+/// The message of the last error is available through `mplz_get_last_error`.
+///
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MplzError {
+    /// Succeeded.
+    Ok = 0,
+    /// A pointer is null.
+    NullPointer = 1,
+    /// A string is not valid UTF-8.
+    InvalidUtf8 = 2,
+    /// A string is not valid JSON of the expected structure.
+    InvalidJson = 3,
+    /// An argument is out of range, such as an id of a player who does not exist.
+    InvalidArgument = 4,
+    /// The library panicked. The session may be left inconsistent, so it should be freed.
+    Panic = 5,
+}
+
+///
+/// A game session. It must be freed with `mplz_session_free`.
+///
+pub struct MplzSession {
+    game: GameSession,
+}
+
+type Failure = (MplzError, String);
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+///
+/// Runs the function, turning its failure and panic into an error code.
+///
+fn run<F: FnOnce() -> Result<(), Failure>>(f: F) -> MplzError {
+    let result = catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let msg = payload
+            .downcast_ref::<&str>()
+            .map(|msg| msg.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Unknown panic.".to_string());
+        Err((MplzError::Panic, msg))
+    });
+
+    match result {
+        Ok(()) => MplzError::Ok,
+        Err((code, msg)) => {
+            LAST_ERROR.with(|last| *last.borrow_mut() = CString::new(msg).ok());
+            code
+        }
+    }
+}
+
+fn null_pointer(name: &str) -> Failure {
+    (MplzError::NullPointer, format!("\"{}\" is null.", name))
+}
+
+unsafe fn get_session<'a>(session: *const MplzSession) -> Result<&'a MplzSession, Failure> {
+    session.as_ref().ok_or_else(|| null_pointer("session"))
+}
+
+unsafe fn get_session_mut<'a>(session: *mut MplzSession) -> Result<&'a mut MplzSession, Failure> {
+    session.as_mut().ok_or_else(|| null_pointer("session"))
+}
+
+unsafe fn write<T>(out: *mut T, value: T) -> Result<(), Failure> {
+    if out.is_null() {
+        return Err(null_pointer("out"));
+    }
+    out.write(value);
+    Ok(())
+}
+
+fn check_player(session: &MplzSession, player_id: usize) -> Result<(), Failure> {
+    if player_id < session.game.players.len() {
+        Ok(())
+    } else {
+        Err((
+            MplzError::InvalidArgument,
+            format!("PLAYER{} does not exist.", player_id),
+        ))
+    }
+}
+
+///
+/// Gets the message of the last error on the thread, or null if no error has occurred.
+///
+/// The string is owned by the library, and valid until the next error on the thread.
+///
+#[no_mangle]
+pub extern "C" fn mplz_get_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(null(), |msg| msg.as_ptr()))
+}
+
+///
+/// Creates an initialized game with `player_num` players.
+///
+/// # Safety
+///
+/// `out` must be a valid pointer to write the session to.
+///
+#[no_mangle]
+pub unsafe extern "C" fn mplz_session_new(
+    player_num: u32,
+    out: *mut *mut MplzSession,
+) -> MplzError {
+    run(|| {
+        if player_num == 0 {
+            return Err((
+                MplzError::InvalidArgument,
+                "A game needs a player at least.".to_string(),
+            ));
+        }
+
+        let session = Box::new(MplzSession {
+            game: GameSession::new(player_num),
+        });
+        write(out, Box::into_raw(session))
+    })
+}
+
+///
+/// Creates a game from JSON of `IGameInfo`.
+///
+/// # Safety
+///
+/// `json` must be a null-terminated string, and `out` must be a valid pointer to write the session to.
+///
+#[no_mangle]
+pub unsafe extern "C" fn mplz_session_from_json(
+    json: *const c_char,
+    out: *mut *mut MplzSession,
+) -> MplzError {
+    run(|| {
+        if json.is_null() {
+            return Err(null_pointer("json"));
+        }
+        let json = CStr::from_ptr(json)
+            .to_str()
+            .map_err(|err| (MplzError::InvalidUtf8, err.to_string()))?;
+        let info: GameInfo =
+            serde_json::from_str(json).map_err(|err| (MplzError::InvalidJson, err.to_string()))?;
+        let game =
+            GameSession::from_info(&info).map_err(|msg| (MplzError::InvalidArgument, msg))?;
+
+        let session = Box::new(MplzSession { game });
+        write(out, Box::into_raw(session))
+    })
+}
+
+///
+/// Serializes the game into JSON of `IGameInfo`. The string must be freed with `mplz_string_free`.
+///
+/// # Safety
+///
+/// `session` must be a session created by the library, and `out` must be a valid pointer to write the string to.
+///
+#[no_mangle]
+pub unsafe extern "C" fn mplz_session_to_json(
+    session: *const MplzSession,
+    out: *mut *mut c_char,
+) -> MplzError {
+    run(|| {
+        let session = get_session(session)?;
+        let json = CString::new(session.game.to_json()).unwrap();
+        write(out, json.into_raw())
+    })
+}
+
+///
+/// Simulates `turns` turns.
+///
+/// # Safety
+///
+/// `session` must be a session created by the library.
+///
+#[no_mangle]
+pub unsafe extern "C" fn mplz_session_step(session: *mut MplzSession, turns: u32) -> MplzError {
+    run(|| {
+        let session = get_session_mut(session)?;
+        GameCommand::Step(turns, &mut session.game)
+            .execute()
+            .map_err(|err| (MplzError::InvalidArgument, err.to_string()))
+    })
+}
+
+///
+/// Gets the number of the players.
+///
+/// # Safety
+///
+/// `session` must be a session created by the library, and `out` must be a valid pointer.
+///
+#[no_mangle]
+pub unsafe extern "C" fn mplz_session_get_player_num(
+    session: *const MplzSession,
+    out: *mut usize,
+) -> MplzError {
+    run(|| {
+        let session = get_session(session)?;
+        write(out, session.game.players.len())
+    })
+}
+
+///
+/// Gets TAP of the player.
+///
+/// # Safety
+///
+/// `session` must be a session created by the library, and `out` must be a valid pointer.
+///
+#[no_mangle]
+pub unsafe extern "C" fn mplz_session_get_tap(
+    session: *const MplzSession,
+    player_id: usize,
+    out: *mut u32,
+) -> MplzError {
+    run(|| {
+        let session = get_session(session)?;
+        check_player(session, player_id)?;
+
+        let player = session.game.get_player(player_id);
+        write(out, Appraiser::get_tap(player, &session.game.board))
+    })
+}
+
+///
+/// Gets the money which the player can pay, including what they get by selling and mortgaging.
+///
+/// # Safety
+///
+/// `session` must be a session created by the library, and `out` must be a valid pointer.
+///
+#[no_mangle]
+pub unsafe extern "C" fn mplz_session_get_payable_money(
+    session: *const MplzSession,
+    player_id: usize,
+    out: *mut u32,
+) -> MplzError {
+    run(|| {
+        let session = get_session(session)?;
+        check_player(session, player_id)?;
+
+        let player = session.game.get_player(player_id);
+        write(
+            out,
+            Appraiser::get_payable_money(player, &session.game.board),
+        )
+    })
+}
+
+///
+/// Simulates `depth` turns `iteration` times, and writes the survival rates of the players to `rates`.
+///
+/// `len` is the length of `rates`, which must be the number of the players or more.
+///
+/// # Safety
+///
+/// `session` must be a session created by the library, and `rates` must be a valid array of `len` elements.
+///
+#[no_mangle]
+pub unsafe extern "C" fn mplz_session_get_survival(
+    session: *const MplzSession,
+    iteration: u32,
+    depth: usize,
+    rates: *mut f32,
+    len: usize,
+) -> MplzError {
+    run(|| {
+        let session = get_session(session)?;
+        if rates.is_null() {
+            return Err(null_pointer("rates"));
+        }
+        if len < session.game.players.len() {
+            return Err((
+                MplzError::InvalidArgument,
+                "\"rates\" is shorter than the number of the players.".to_string(),
+            ));
+        }
+
+        let simulation = Simulation::from_session(&session.game, iteration, depth);
+        let result = simulation.get_survival();
+        for (idx, rate) in result.survival_rates.into_iter().enumerate() {
+            rates.add(idx).write(rate);
+        }
+        Ok(())
+    })
+}
+
+///
+/// Frees the session. Does nothing if it is null.
+///
+/// # Safety
+///
+/// `session` must be a session created by the library, which has not been freed yet.
+///
+#[no_mangle]
+pub unsafe extern "C" fn mplz_session_free(session: *mut MplzSession) {
+    if !session.is_null() {
+        drop(Box::from_raw(session));
+    }
+}
+
+///
+/// Frees the string returned by the library. Does nothing if it is null.
+///
+/// # Safety
+///
+/// `string` must be a string returned by the library, which has not been freed yet.
+///
+#[no_mangle]
+pub unsafe extern "C" fn mplz_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
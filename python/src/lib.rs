@@ -0,0 +1,168 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::Serialize;
+
+use mplz_core::appraiser::Appraiser;
+use mplz_core::board::GameSession;
+use mplz_core::command::GameCommand;
+use mplz_core::serialization::GameInfo;
+use mplz_core::simulation::Simulation;
+
+#[derive(Serialize)]
+struct MoneyBody {
+    money: Vec<u32>,
+    available: Vec<u32>,
+    total: Vec<u32>,
+}
+
+///
+/// Converts the value into plain Python objects through JSON, so that it can be fed into pandas directly.
+///
+fn to_object<'py, T: Serialize>(py: Python<'py>, value: &T) -> PyResult<Bound<'py, PyAny>> {
+    let json = serde_json::to_string(value).unwrap();
+    py.import("json")?.call_method1("loads", (json,))
+}
+
+///
+/// A game session.
+///
+/// It is bound to the thread where it is created since the strategies are not shared between threads.
+///
+#[pyclass(name = "GameSession", unsendable)]
+struct PyGameSession {
+    game: GameSession,
+}
+
+#[pymethods]
+impl PyGameSession {
+    ///
+    /// Creates an initialized game with `player_num` players.
+    ///
+    #[new]
+    fn new(player_num: u32) -> PyResult<Self> {
+        if player_num == 0 {
+            return Err(PyValueError::new_err("A game needs a player at least."));
+        }
+
+        Ok(PyGameSession {
+            game: GameSession::new(player_num),
+        })
+    }
+
+    ///
+    /// Creates a game from JSON of `IGameInfo`.
+    ///
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        let info: GameInfo =
+            serde_json::from_str(json).map_err(|err| PyValueError::new_err(err.to_string()))?;
+        let game = GameSession::from_info(&info).map_err(PyValueError::new_err)?;
+
+        Ok(PyGameSession { game })
+    }
+
+    ///
+    /// Serializes the game into JSON of `IGameInfo`.
+    ///
+    fn to_json(&self) -> String {
+        self.game.to_json()
+    }
+
+    ///
+    /// Converts the game into a dict of `IGameInfo`.
+    ///
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        to_object(py, &self.game.to_info())
+    }
+
+    ///
+    /// Fixes the dice of the game to reproduce the simulation.
+    ///
+    fn set_seed(&mut self, seed: u64) {
+        self.game.set_seed(seed);
+    }
+
+    ///
+    /// Simulates `turns` turns.
+    ///
+    #[pyo3(signature = (turns = 1))]
+    fn step(&mut self, turns: u32) -> PyResult<()> {
+        GameCommand::Step(turns, &mut self.game)
+            .execute()
+            .map_err(|err| PyValueError::new_err(err.to_string()))
+    }
+
+    ///
+    /// Gets the number of the players.
+    ///
+    fn get_player_num(&self) -> usize {
+        self.game.players.len()
+    }
+
+    ///
+    /// Gets TAP of the players.
+    ///
+    fn get_taps(&self) -> Vec<u32> {
+        self.game
+            .players
+            .iter()
+            .map(|player| Appraiser::get_tap(player, &self.game.board))
+            .collect()
+    }
+
+    ///
+    /// Analyzes the money of the players, and returns a dict of lists `money`, `available` and `total`.
+    ///
+    fn get_money<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let money = self
+            .game
+            .players
+            .iter()
+            .map(|player| player.money)
+            .collect::<Vec<_>>();
+        let total = self
+            .game
+            .players
+            .iter()
+            .map(|player| Appraiser::get_payable_money(player, &self.game.board))
+            .collect::<Vec<_>>();
+        let available = money
+            .iter()
+            .zip(total.iter())
+            .map(|(money, total)| total - money)
+            .collect();
+        to_object(
+            py,
+            &MoneyBody {
+                money,
+                available,
+                total,
+            },
+        )
+    }
+
+    ///
+    /// Simulates `depth` turns `iteration` times, and returns a dict of `survival_rates` and `statistics`.
+    ///
+    /// Other Python threads keep running while simulating.
+    ///
+    fn get_survival<'py>(
+        &self,
+        py: Python<'py>,
+        iteration: u32,
+        depth: usize,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let simulation = Simulation::from_session(&self.game, iteration, depth);
+        let result = py.detach(move || simulation.get_survival());
+        to_object(py, &result)
+    }
+}
+
+///
+/// Python bindings of Monopolizer.
+///
+#[pymodule]
+fn monopolizer(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyGameSession>()?;
+    Ok(())
+}
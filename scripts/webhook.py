@@ -0,0 +1,51 @@
+"""
+A stand-in decision service for the webhook strategy.
+
+It buys every place offered and passes the other decisions.
+`--delay` makes it respond late, and `--illegal` makes it respond an illegal action,
+so that the fallback of the strategy can be checked.
+
+$ python3 scripts/webhook.py --port 8080
+"""
+
+from argparse import ArgumentParser
+from http.server import BaseHTTPRequestHandler, HTTPServer
+import json
+import time
+
+class DecisionHandler(BaseHTTPRequestHandler):
+    delay: float = 0.0
+    illegal: bool = False
+
+    def do_POST(self):
+        length = int(self.headers["Content-Length"])
+        request = json.loads(self.rfile.read(length))
+
+        decision = request["decision"]
+        actions = []
+        if isinstance(decision, dict) and "Buy" in decision:
+            actions.append({ "Buy": decision["Buy"][0] })
+        if self.illegal:
+            actions.append({ "BuildHouse": 1 })
+        time.sleep(self.delay)
+
+        body = json.dumps({ "actions": actions }).encode()
+        self.send_response(200)
+        self.send_header("Content-Type", "application/json")
+        self.send_header("Content-Length", str(len(body)))
+        self.end_headers()
+        self.wfile.write(body)
+
+    def log_message(self, format, *args):
+        pass
+
+if __name__ == "__main__":
+    parser = ArgumentParser()
+    parser.add_argument("--port", type=int, default=8080)
+    parser.add_argument("--delay", type=float, default=0.0)
+    parser.add_argument("--illegal", action="store_true")
+    args = parser.parse_args()
+
+    DecisionHandler.delay = args.delay
+    DecisionHandler.illegal = args.illegal
+    HTTPServer(("127.0.0.1", args.port), DecisionHandler).serve_forever()
@@ -1,17 +1,56 @@
 use actix_cors::Cors;
 use actix_files::Files;
 use actix_web::http::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE};
-use actix_web::web::{Json, Query, Redirect};
+use actix_web::web::{block, Json, Query, Redirect};
 use actix_web::{get, post, App, HttpResponse, HttpServer, Responder};
 use mplz_core::appraiser::Appraiser;
-use mplz_core::player::PlayerState;
 use serde::{Deserialize, Serialize};
 
+use mplz_core::action::Decision;
 use mplz_core::board::GameSession;
+use mplz_core::observer::StrategyAction;
 use mplz_core::serialization::{GameInfo, PlaceProp};
+use mplz_core::simulation::{Simulation, SimulationHandle};
+use mplz_core::strategy::{MonteCarloStrategy, StrategyRegistry};
+use mplz_core::tournament::{Tournament, TournamentParams};
 
 const MPLZ_API_PORT: u16 = 5391;
 
+///
+/// The maximum number of the turns whose states are returned by the history requested.
+///
+const MAX_HISTORY_TURNS: u32 = 1000;
+
+///
+/// The maximum number of the turns simulated by the step requested.
+///
+const MAX_STEP_TURNS: u32 = 1000;
+
+///
+/// The maximum number of the games simulated for the survival rates requested.
+///
+const MAX_SURVIVAL_GAMES: u32 = 1000;
+
+///
+/// The maximum number of the turns of a game simulated for the survival rates requested.
+///
+const MAX_SURVIVAL_TURNS: u32 = 1000;
+
+///
+/// The maximum number of the strategies in a tournament requested.
+///
+const MAX_TOURNAMENT_STRATEGIES: usize = 8;
+
+///
+/// The maximum number of the games for each seat order in a tournament requested.
+///
+const MAX_TOURNAMENT_GAMES: u32 = 100;
+
+///
+/// The maximum number of the turns of a game in a tournament requested.
+///
+const MAX_TOURNAMENT_TURNS: usize = 1000;
+
 #[cfg(debug_assertions)]
 const MPLZ_WEB_PORT: u16 = 5390;
 
@@ -39,11 +78,84 @@ struct StepBody {
 
 #[post("/step")]
 async fn step(body: Json<StepBody>) -> impl Responder {
-    let mut session = GameSession::from_info(&body.game);
+    let StepBody { game, num } = body.into_inner();
+    if num > MAX_STEP_TURNS {
+        return HttpResponse::BadRequest().body(format!(
+            "A step can simulate {} turns at most.",
+            MAX_STEP_TURNS
+        ));
+    }
+
+    let mut session = match GameSession::from_info(&game) {
+        Ok(session) => session,
+        Err(msg) => return HttpResponse::BadRequest().body(msg),
+    };
+
+    // Runs on the blocking thread pool not to block the worker.
+    let json = block(move || {
+        for _ in 0..num {
+            session.spend_one_turn();
+        }
+        session.to_json()
+    })
+    .await
+    .unwrap();
+    HttpResponse::Ok().body(json)
+}
+
+#[derive(Serialize)]
+struct HistoryBody {
+    history: Vec<GameInfo>,
+}
+
+#[post("/history")]
+async fn history(body: Json<StepBody>) -> impl Responder {
+    if body.num > MAX_HISTORY_TURNS {
+        return HttpResponse::BadRequest().body(format!(
+            "The history can have {} turns at most.",
+            MAX_HISTORY_TURNS
+        ));
+    }
+
+    let mut session = match GameSession::from_info(&body.game) {
+        Ok(session) => session,
+        Err(msg) => return HttpResponse::BadRequest().body(msg),
+    };
+    session.set_history_capacity(body.num as usize);
     for _ in 0..body.num {
         session.spend_one_turn();
     }
-    HttpResponse::Ok().body(session.to_json())
+    let body = HistoryBody {
+        history: session.get_history_infos(),
+    };
+    HttpResponse::Ok().body(serde_json::to_string_pretty(&body).unwrap())
+}
+
+#[derive(Deserialize)]
+struct ActionsRequest {
+    game: GameInfo,
+    player_id: usize,
+    decision: Decision,
+}
+
+#[derive(Serialize)]
+struct ActionsBody {
+    actions: Vec<StrategyAction>,
+}
+
+#[post("/actions")]
+async fn actions(body: Json<ActionsRequest>) -> impl Responder {
+    let session = match GameSession::from_info(&body.game) {
+        Ok(session) => session,
+        Err(msg) => return HttpResponse::BadRequest().body(msg),
+    };
+    if body.player_id >= session.players.len() {
+        return HttpResponse::BadRequest().body("The player does not exist.");
+    }
+    let body = ActionsBody {
+        actions: session.get_legal_actions(body.player_id, &body.decision),
+    };
+    HttpResponse::Ok().body(serde_json::to_string_pretty(&body).unwrap())
 }
 
 #[derive(Serialize)]
@@ -53,7 +165,10 @@ struct PlacesBody {
 
 #[post("/places")]
 async fn places(body: Json<GameInfo>) -> impl Responder {
-    let session = GameSession::from_info(&body);
+    let session = match GameSession::from_info(&body) {
+        Ok(session) => session,
+        Err(msg) => return HttpResponse::BadRequest().body(msg),
+    };
     let places = session
         .board
         .places
@@ -71,7 +186,10 @@ struct TapBody {
 
 #[post("/tap")]
 async fn tap(body: Json<GameInfo>) -> impl Responder {
-    let session = GameSession::from_info(&body);
+    let session = match GameSession::from_info(&body) {
+        Ok(session) => session,
+        Err(msg) => return HttpResponse::BadRequest().body(msg),
+    };
     let taps = session
         .players
         .iter()
@@ -90,7 +208,10 @@ struct MoneyBody {
 
 #[post("/money")]
 async fn money(body: Json<GameInfo>) -> impl Responder {
-    let session = GameSession::from_info(&body);
+    let session = match GameSession::from_info(&body) {
+        Ok(session) => session,
+        Err(msg) => return HttpResponse::BadRequest().body(msg),
+    };
     let money = session
         .players
         .iter()
@@ -121,39 +242,83 @@ struct SurvivalRequest {
     depth: u32,
 }
 
-#[derive(Serialize)]
-struct SurvivalResponse {
-    survival_rates: Vec<f32>,
+///
+/// Cancels the simulation if the handler is dropped before it finishes, e.g. on shutting down the server.
+///
+struct CancelOnDrop(SimulationHandle);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
 }
 
 #[post("/survival")]
 async fn survival(body: Json<SurvivalRequest>) -> impl Responder {
-    let mut counter = vec![0_u32; body.game.players.len()];
-    for _ in 0..body.num {
-        let mut session = GameSession::from_info(&body.game);
-        for _ in 0..body.depth {
-            session.spend_one_turn();
-        }
-        for (idx, cnt) in counter.iter_mut().enumerate() {
-            let player = session.get_player(idx);
-            match player.state {
-                PlayerState::Bankrupted => {}
-                _ => *cnt += 1,
-            }
-        }
+    let SurvivalRequest { game, num, depth } = body.into_inner();
+    if num > MAX_SURVIVAL_GAMES {
+        return HttpResponse::BadRequest().body(format!(
+            "The survival rates can be calculated from {} games at most.",
+            MAX_SURVIVAL_GAMES
+        ));
     }
-    let survival_rates = counter
-        .iter()
-        .map(|&count| count as f32 / body.num as f32)
-        .collect();
-    let body = SurvivalResponse { survival_rates };
+    if depth > MAX_SURVIVAL_TURNS {
+        return HttpResponse::BadRequest().body(format!(
+            "A game for the survival rates can last {} turns at most.",
+            MAX_SURVIVAL_TURNS
+        ));
+    }
+
+    let simulation = match Simulation::new(game, num, depth as usize) {
+        Ok(simulation) => simulation,
+        Err(msg) => return HttpResponse::BadRequest().body(msg),
+    };
+    let _guard = CancelOnDrop(simulation.get_handle());
+
+    // Runs on the blocking thread pool not to block the worker.
+    let body = block(move || simulation.get_survival()).await.unwrap();
     HttpResponse::Ok().body(serde_json::to_string_pretty(&body).unwrap())
 }
 
+#[post("/tournament")]
+async fn tournament(body: Json<TournamentParams>) -> impl Responder {
+    let params = body.into_inner();
+    if params.strategies.len() > MAX_TOURNAMENT_STRATEGIES {
+        return HttpResponse::BadRequest().body(format!(
+            "A tournament can have {} strategies at most.",
+            MAX_TOURNAMENT_STRATEGIES
+        ));
+    }
+    if params.games > MAX_TOURNAMENT_GAMES {
+        return HttpResponse::BadRequest().body(format!(
+            "A tournament can play {} games at most for each seat order.",
+            MAX_TOURNAMENT_GAMES
+        ));
+    }
+    if params.max_turns > MAX_TOURNAMENT_TURNS {
+        return HttpResponse::BadRequest().body(format!(
+            "A game of a tournament can last {} turns at most.",
+            MAX_TOURNAMENT_TURNS
+        ));
+    }
+
+    // Runs on the blocking thread pool not to block the worker.
+    match block(move || Tournament::new(params).run()).await.unwrap() {
+        Ok(result) => HttpResponse::Ok().body(serde_json::to_string_pretty(&result).unwrap()),
+        Err(msg) => HttpResponse::BadRequest().body(msg),
+    }
+}
+
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     println!("Starting the server...");
 
+    // The games come from anyone, so the strategies starting processes or sending requests are never available.
+    // Neither is the Monte Carlo one, whose search can take as long as the request asks.
+    let mut registry = StrategyRegistry::default();
+    registry.unregister(MonteCarloStrategy::NAME);
+    StrategyRegistry::install(registry).unwrap();
+
     HttpServer::new(|| {
         let cors = if cfg!(debug_assertions) {
             Cors::default()
@@ -171,10 +336,13 @@ async fn main() -> std::io::Result<()> {
             .service(root)
             .service(init)
             .service(step)
+            .service(history)
+            .service(actions)
             .service(places)
             .service(tap)
             .service(money)
             .service(survival)
+            .service(tournament)
             .service(
                 Files::new("/", "./web/build/")
                     .prefer_utf8(true)
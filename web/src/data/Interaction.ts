@@ -1,9 +1,15 @@
+export interface IStrategyInfo {
+    name: string;
+    params: unknown;
+}
+
 export interface IPlayerInfo {
     player_id: number;
     money: number;
     is_bankrupted: boolean;
     jail_turn: number | undefined;
     position: number;
+    strategy: IStrategyInfo | undefined;
 }
 
 export interface IPlaceInfo {
@@ -135,3 +141,26 @@ export async function fetchSurvival(
     const result = (await response.json()) as { survival_rates: number[] };
     return result.survival_rates;
 }
+
+export type IDecision =
+    | { Raise: number }
+    | "Invest"
+    | { Buy: [number, number] }
+    | "LeaveJail";
+
+export interface ITradeOffer {
+    partner: number;
+    give: number[];
+    take: number[];
+    give_money: number;
+    take_money: number;
+}
+
+export type IStrategyAction =
+    | { BuildHouse: number }
+    | { SellHouse: number }
+    | { Mortgage: number }
+    | { Unmortgage: number }
+    | { Buy: number }
+    | { Trade: ITradeOffer }
+    | "PayJailFee";